@@ -0,0 +1,32 @@
+//! Assertions shared by the property tests of the diff algorithms.
+
+use crate::std::fmt::Debug;
+use crate::{apply, Diff};
+use proptest::prelude::*;
+
+/// Asserts that applying the diffs to the left sequence gives the right
+/// sequence and that each common subsequence is equal in both sequences.
+pub fn assert_diffs_transform<T>(
+    left: &[T],
+    right: &[T],
+    diffs: &[Diff],
+) -> Result<(), TestCaseError>
+where
+    T: PartialEq + Clone + Debug,
+{
+    prop_assert_eq!(apply(left, right, diffs), Ok(right.to_vec()));
+    for diff in diffs {
+        if let Diff::Both {
+            left_index,
+            right_index,
+            length,
+        } = *diff
+        {
+            prop_assert_eq!(
+                &left[left_index..left_index + length],
+                &right[right_index..right_index + length]
+            );
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,456 @@
+//! Sliding of inserted and removed lines to natural boundaries.
+//!
+//! This is a port of the sliding of changes of git, including the indent
+//! heuristic, which was developed by Michael Haggerty. The weights and
+//! penalties are the ones used by git.
+
+use crate::lines::lines;
+use crate::myers::DiffList;
+use crate::std::vec;
+use crate::std::vec::Vec;
+use crate::Diff;
+
+/// Max number of lines a block is slid to find a better position.
+const MAX_SLIDING: usize = 100;
+/// Indentations beyond this value are considered equal.
+const MAX_INDENT: isize = 200;
+/// Blank lines beyond this number are not counted.
+const MAX_BLANKS: usize = 20;
+
+const START_OF_FILE_PENALTY: isize = 1;
+const END_OF_FILE_PENALTY: isize = 21;
+const TOTAL_BLANK_WEIGHT: isize = -30;
+const POST_BLANK_WEIGHT: isize = 6;
+const RELATIVE_INDENT_PENALTY: isize = -4;
+const RELATIVE_INDENT_WITH_BLANK_PENALTY: isize = 10;
+const RELATIVE_OUTDENT_PENALTY: isize = 24;
+const RELATIVE_OUTDENT_WITH_BLANK_PENALTY: isize = 17;
+const RELATIVE_DEDENT_PENALTY: isize = 23;
+const RELATIVE_DEDENT_WITH_BLANK_PENALTY: isize = 17;
+const INDENT_WEIGHT: isize = 60;
+
+/// Slides blocks of inserted or removed lines to the most natural
+/// boundaries.
+///
+/// A block of inserted or removed lines can often be shifted up or down
+/// without changing the meaning of the diff, because it starts with the same
+/// lines as the ones following it, like closing braces or blank lines. The
+/// diff algorithm picks one of these positions, which is not necessarily the
+/// one a human would choose. This cleanup slides the blocks like git does
+/// after finding the differences:
+///
+/// 1. The removed lines and the inserted lines are slid independently. A
+///    block that touches another block of the same text while sliding is
+///    merged with it.
+/// 2. A block that can be slid next to a block of the other text is slid to
+///    the last such position, so that removed and inserted lines are shown
+///    together.
+/// 3. Any other block is moved to the position that is rated best by the
+///    indent heuristic of git, which prefers blocks that are separated from
+///    their surrounding by blank lines and whose boundaries are at lines with
+///    less indentation.
+///
+/// The diffs must be the ones returned by [`diff_lines()`] for the same two
+/// texts.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_lines, indent_heuristic, Diff};
+///
+/// let left = "fn a() {\n}\n\nfn c() {\n}\n";
+/// let right = "fn a() {\n}\n\nfn b() {\n}\n\nfn c() {\n}\n";
+///
+/// let mut diffs = vec![
+///     Diff::Both { left_index: 0, right_index: 0, length: 1 },
+///     Diff::Right { index: 1, length: 3 },
+///     Diff::Both { left_index: 1, right_index: 4, length: 4 },
+/// ];
+///
+/// indent_heuristic(left, right, &mut diffs);
+///
+/// assert_eq!(
+///     diffs,
+///     [
+///         Diff::Both { left_index: 0, right_index: 0, length: 3 },
+///         Diff::Right { index: 3, length: 3 },
+///         Diff::Both { left_index: 3, right_index: 6, length: 2 },
+///     ]
+/// );
+/// ```
+///
+/// [`diff_lines()`]: crate::diff_lines
+pub fn indent_heuristic(left: &str, right: &str, diffs: &mut Vec<Diff>) {
+    let left = lines(left);
+    let right = lines(right);
+
+    let mut left_changed = vec![false; left.len()];
+    let mut right_changed = vec![false; right.len()];
+    for diff in diffs.iter() {
+        let (changed, index, length) = match *diff {
+            Diff::Left { index, length } => (&mut left_changed, index, length),
+            Diff::Right { index, length } => (&mut right_changed, index, length),
+            Diff::Both { .. } => continue,
+        };
+        if let Some(changed) = changed.get_mut(index..index.saturating_add(length)) {
+            changed.fill(true);
+        }
+    }
+    let (original_left_changed, original_right_changed) =
+        (left_changed.clone(), right_changed.clone());
+
+    compact(&left, &mut left_changed, &right_changed);
+    compact(&right, &mut right_changed, &left_changed);
+
+    if left_changed != original_left_changed || right_changed != original_right_changed {
+        *diffs = diffs_of_changes(&left_changed, &right_changed);
+    }
+}
+
+/// Slides the blocks of changed lines of one text, while keeping track of
+/// the blocks of the other text that are aligned with them.
+///
+/// This is a port of `xdl_change_compact()` of git.
+fn compact(lines: &[&str], changed: &mut [bool], other_changed: &[bool]) {
+    let mut block = Block::first(changed);
+    let mut other_block = Block::first(other_changed);
+    loop {
+        if !block.is_empty() {
+            // Slide the block up and down as far as possible, until it does
+            // not grow anymore by merging with adjacent blocks.
+            let (earliest_end, end_next_to_other) = loop {
+                let length = block.len();
+                let mut end_next_to_other = None;
+
+                while block.slide_up(lines, changed) {
+                    other_block.previous(other_changed);
+                }
+                let earliest_end = block.end;
+                if !other_block.is_empty() {
+                    end_next_to_other = Some(block.end);
+                }
+
+                while block.slide_down(lines, changed) {
+                    other_block.next(other_changed);
+                    if !other_block.is_empty() {
+                        end_next_to_other = Some(block.end);
+                    }
+                }
+
+                if length == block.len() {
+                    break (earliest_end, end_next_to_other);
+                }
+            };
+
+            if block.end == earliest_end {
+                // The block can not be slid.
+            } else if end_next_to_other.is_some() {
+                while other_block.is_empty() && block.slide_up(lines, changed) {
+                    other_block.previous(other_changed);
+                }
+            } else {
+                let best_end = best_end(lines, earliest_end, block.end, block.len());
+                while block.end > best_end && block.slide_up(lines, changed) {
+                    other_block.previous(other_changed);
+                }
+            }
+        }
+
+        if !block.next(changed) {
+            break;
+        }
+        other_block.next(other_changed);
+    }
+}
+
+/// Converts the changed lines of both texts into diffs.
+fn diffs_of_changes(left_changed: &[bool], right_changed: &[bool]) -> Vec<Diff> {
+    let mut diffs = DiffList::default();
+    let mut left_index = 0;
+    let mut right_index = 0;
+    while left_index < left_changed.len() || right_index < right_changed.len() {
+        let removed = run_end(left_changed, left_index) - left_index;
+        let inserted = run_end(right_changed, right_index) - right_index;
+        diffs.push_left(left_index, removed);
+        diffs.push_right(right_index, inserted);
+        left_index += removed;
+        right_index += inserted;
+
+        let common = left_changed[left_index..]
+            .iter()
+            .zip(&right_changed[right_index..])
+            .take_while(|(left, right)| !**left && !**right)
+            .count();
+        if removed + inserted + common == 0 {
+            break;
+        }
+        diffs.push_both(left_index, right_index, common);
+        left_index += common;
+        right_index += common;
+    }
+    diffs.into_vec()
+}
+
+/// Returns the index after the run of changed lines starting at `start`.
+fn run_end(changed: &[bool], start: usize) -> usize {
+    start
+        + changed[start..]
+            .iter()
+            .take_while(|changed| **changed)
+            .count()
+}
+
+/// Returns the index of the first line of the run of changed lines ending at
+/// `end`.
+fn run_start(changed: &[bool], end: usize) -> usize {
+    end - changed[..end]
+        .iter()
+        .rev()
+        .take_while(|changed| **changed)
+        .count()
+}
+
+/// A block of changed lines of one text. An empty block is located at the
+/// unchanged line following the previous block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Block {
+    start: usize,
+    end: usize,
+}
+
+impl Block {
+    /// Returns the first block of the text, which is empty if the first line
+    /// is unchanged.
+    fn first(changed: &[bool]) -> Self {
+        Self {
+            start: 0,
+            end: run_end(changed, 0),
+        }
+    }
+
+    const fn len(self) -> usize {
+        self.end - self.start
+    }
+
+    const fn is_empty(self) -> bool {
+        self.start == self.end
+    }
+
+    /// Moves to the block after the next unchanged line. Returns `false` if
+    /// this is the last block.
+    fn next(&mut self, changed: &[bool]) -> bool {
+        if self.end == changed.len() {
+            return false;
+        }
+        self.start = self.end + 1;
+        self.end = run_end(changed, self.start);
+        true
+    }
+
+    /// Moves to the block before the previous unchanged line. Returns `false`
+    /// if this is the first block.
+    fn previous(&mut self, changed: &[bool]) -> bool {
+        if self.start == 0 {
+            return false;
+        }
+        self.end = self.start - 1;
+        self.start = run_start(changed, self.end);
+        true
+    }
+
+    /// Slides the block down by one line, if its first line equals the line
+    /// after it, and merges it with a following block it touches.
+    fn slide_down(&mut self, lines: &[&str], changed: &mut [bool]) -> bool {
+        if self.end == lines.len() || lines[self.start] != lines[self.end] {
+            return false;
+        }
+        changed[self.start] = false;
+        changed[self.end] = true;
+        self.start += 1;
+        self.end = run_end(changed, self.end + 1);
+        true
+    }
+
+    /// Slides the block up by one line, if its last line equals the line
+    /// before it, and merges it with a preceding block it touches.
+    fn slide_up(&mut self, lines: &[&str], changed: &mut [bool]) -> bool {
+        if self.start == 0 || lines[self.start - 1] != lines[self.end - 1] {
+            return false;
+        }
+        changed[self.start - 1] = true;
+        changed[self.end - 1] = false;
+        self.start = run_start(changed, self.start - 1);
+        self.end -= 1;
+        true
+    }
+}
+
+/// Returns the end of the block of the given `length` between
+/// `earliest_end` and `latest_end` that is rated best.
+///
+/// Like git, only the positions near the `latest_end` are rated. If two
+/// positions are rated equally, the later one is chosen.
+fn best_end(lines: &[&str], earliest_end: usize, latest_end: usize, length: usize) -> usize {
+    let score_at = |end: usize| {
+        let mut score = SplitScore::default();
+        score.add(&SplitMeasurement::new(lines, end));
+        score.add(&SplitMeasurement::new(lines, end - length));
+        score
+    };
+
+    let first_end = earliest_end
+        .max(latest_end.saturating_sub(length + 1))
+        .max(latest_end.saturating_sub(MAX_SLIDING));
+    let mut best = (first_end, score_at(first_end));
+    for end in first_end + 1..=latest_end {
+        let score = score_at(end);
+        if score.is_not_worse_than(&best.1) {
+            best = (end, score);
+        }
+    }
+    best.0
+}
+
+/// Properties of the lines around a split between two lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SplitMeasurement {
+    /// Whether the split is at the end of the text.
+    end_of_file: bool,
+    /// Indentation of the line after the split, `None` if it is blank.
+    indent: Option<isize>,
+    /// Number of blank lines before the split.
+    pre_blank: usize,
+    /// Indentation of the first non-blank line before the split.
+    pre_indent: Option<isize>,
+    /// Number of blank lines after the line following the split.
+    post_blank: usize,
+    /// Indentation of the first non-blank line after the line following the
+    /// split.
+    post_indent: Option<isize>,
+}
+
+impl SplitMeasurement {
+    /// Measures the split before the line `split`.
+    fn new(lines: &[&str], split: usize) -> Self {
+        let (end_of_file, indent) = lines
+            .get(split)
+            .map_or((true, None), |line| (false, indentation(line)));
+
+        let mut pre_blank = 0;
+        let mut pre_indent = None;
+        for line in lines.iter().take(split).rev() {
+            pre_indent = indentation(line);
+            if pre_indent.is_some() {
+                break;
+            }
+            pre_blank += 1;
+            if pre_blank == MAX_BLANKS {
+                pre_indent = Some(0);
+                break;
+            }
+        }
+
+        let mut post_blank = 0;
+        let mut post_indent = None;
+        for line in lines.iter().skip(split + 1) {
+            post_indent = indentation(line);
+            if post_indent.is_some() {
+                break;
+            }
+            post_blank += 1;
+            if post_blank == MAX_BLANKS {
+                post_indent = Some(0);
+                break;
+            }
+        }
+
+        Self {
+            end_of_file,
+            indent,
+            pre_blank,
+            pre_indent,
+            post_blank,
+            post_indent,
+        }
+    }
+}
+
+/// Rating of the splits at the boundaries of a block. Lower is better.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct SplitScore {
+    effective_indent: isize,
+    penalty: isize,
+}
+
+impl SplitScore {
+    /// Adds the rating of the measured split.
+    #[allow(clippy::cast_possible_wrap)]
+    fn add(&mut self, split: &SplitMeasurement) {
+        if split.pre_indent.is_none() && split.pre_blank == 0 {
+            self.penalty += START_OF_FILE_PENALTY;
+        }
+        if split.end_of_file {
+            self.penalty += END_OF_FILE_PENALTY;
+        }
+
+        let post_blank = if split.indent.is_none() {
+            1 + split.post_blank as isize
+        } else {
+            0
+        };
+        let total_blank = split.pre_blank as isize + post_blank;
+        self.penalty += TOTAL_BLANK_WEIGHT * total_blank;
+        self.penalty += POST_BLANK_WEIGHT * post_blank;
+
+        let indent = split.indent.or(split.post_indent);
+        let any_blanks = total_blank != 0;
+        self.effective_indent += indent.unwrap_or(-1);
+
+        let (Some(indent), Some(pre_indent)) = (indent, split.pre_indent) else {
+            return;
+        };
+        if indent > pre_indent {
+            self.penalty += if any_blanks {
+                RELATIVE_INDENT_WITH_BLANK_PENALTY
+            } else {
+                RELATIVE_INDENT_PENALTY
+            };
+        } else if indent < pre_indent {
+            let outdent = split
+                .post_indent
+                .is_some_and(|post_indent| post_indent > indent);
+            self.penalty += match (outdent, any_blanks) {
+                (true, true) => RELATIVE_OUTDENT_WITH_BLANK_PENALTY,
+                (true, false) => RELATIVE_OUTDENT_PENALTY,
+                (false, true) => RELATIVE_DEDENT_WITH_BLANK_PENALTY,
+                (false, false) => RELATIVE_DEDENT_PENALTY,
+            };
+        }
+    }
+
+    /// Returns whether this score is rated equal to or better than the other
+    /// one.
+    fn is_not_worse_than(&self, other: &Self) -> bool {
+        let compared_indents = isize::from(self.effective_indent > other.effective_indent)
+            - isize::from(self.effective_indent < other.effective_indent);
+        INDENT_WEIGHT * compared_indents + self.penalty - other.penalty <= 0
+    }
+}
+
+/// Returns the indentation of the line, where a tab advances to the next
+/// multiple of 8. Returns `None` for a blank line.
+fn indentation(line: &str) -> Option<isize> {
+    let mut indent = 0;
+    for char in line.chars() {
+        match char {
+            ' ' => indent += 1,
+            '\t' => indent += 8 - indent % 8,
+            _ if char.is_whitespace() => {},
+            _ => return Some(indent),
+        }
+        if indent >= MAX_INDENT {
+            return Some(MAX_INDENT);
+        }
+    }
+    None
+}
@@ -0,0 +1,256 @@
+//! Post-processing of diffs to make them easier to read for humans.
+//!
+//! The semantic and the efficiency cleanup follow the ones of the
+//! [diff-match-patch] library by Neil Fraser. As the diffs only contain
+//! indices and lengths, these cleanups decide by the lengths of the common
+//! subsequences and the changes only.
+//!
+//! [diff-match-patch]: https://github.com/google/diff-match-patch
+
+mod indent;
+
+pub use indent::indent_heuristic;
+
+use crate::std::vec::Vec;
+use crate::Diff;
+
+/// The edit cost used by [`cleanup_efficiency()`] by default.
+pub const DEFAULT_EDIT_COST: usize = 4;
+
+/// Merges short common subsequences into the surrounding changes.
+///
+/// A minimal diff often contains tiny common subsequences that are
+/// meaningless to a human reader, like a single shared space between two
+/// larger replacements. This cleanup replaces a [`Diff::Both`] by removing
+/// and inserting its elements, if it is not longer than the changes on
+/// either side of it. The result is no longer minimal, but consists of fewer
+/// and larger changes.
+///
+/// The diffs must be in ascending order as returned by [`diff()`].
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{cleanup_semantic, diff_str, Diff};
+///
+/// let mut diffs = diff_str("mouse", "sofas");
+///
+/// cleanup_semantic(&mut diffs);
+///
+/// assert_eq!(
+///     diffs,
+///     [
+///         Diff::Left { index: 0, length: 5 },
+///         Diff::Right { index: 0, length: 5 },
+///     ]
+/// );
+/// ```
+///
+/// [`diff()`]: crate::diff
+pub fn cleanup_semantic(diffs: &mut Vec<Diff>) {
+    eliminate_equalities(diffs, |length, before, after| {
+        length <= before.left_length.max(before.right_length)
+            && length <= after.left_length.max(after.right_length)
+    });
+}
+
+/// Merges common subsequences into the surrounding changes where this
+/// reduces the number of edit operations.
+///
+/// The `edit_cost` is the cost of an edit operation in terms of elements.
+/// A [`Diff::Both`] shorter than the edit cost is replaced by removing and
+/// inserting its elements, if it is surrounded by removals and insertions on
+/// both sides. A [`Diff::Both`] shorter than half the edit cost is replaced as
+/// well, if it is surrounded by three of them. This is useful for diffs that
+/// are processed by machines, where each edit operation has a fixed cost,
+/// like [`DEFAULT_EDIT_COST`].
+///
+/// The diffs must be in ascending order as returned by [`diff()`].
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{cleanup_efficiency, diff_str, Diff};
+///
+/// let mut diffs = diff_str("ab12cd", "xy12zw");
+///
+/// cleanup_efficiency(&mut diffs, 4);
+///
+/// assert_eq!(
+///     diffs,
+///     [
+///         Diff::Left { index: 0, length: 6 },
+///         Diff::Right { index: 0, length: 6 },
+///     ]
+/// );
+/// ```
+///
+/// [`diff()`]: crate::diff
+pub fn cleanup_efficiency(diffs: &mut Vec<Diff>, edit_cost: usize) {
+    eliminate_equalities(diffs, |length, before, after| {
+        let operations = [
+            before.left_length > 0,
+            before.right_length > 0,
+            after.left_length > 0,
+            after.right_length > 0,
+        ]
+        .into_iter()
+        .filter(|operation| *operation)
+        .count();
+        length < edit_cost && (operations == 4 || (operations == 3 && 2 * length < edit_cost))
+    });
+}
+
+/// A run of changes between two common subsequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Change {
+    left_index: usize,
+    left_length: usize,
+    right_index: usize,
+    right_length: usize,
+}
+
+/// A common subsequence or a run of changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Segment {
+    Equal {
+        left_index: usize,
+        right_index: usize,
+        length: usize,
+    },
+    Change(Change),
+}
+
+/// Replaces each common subsequence, for which `eliminate` returns `true`,
+/// and its surrounding changes by one change.
+///
+/// The function `eliminate` is called with the length of the common
+/// subsequence and the changes before and after it. After a common
+/// subsequence has been eliminated, the previous one is checked again, as
+/// the change following it has grown.
+fn eliminate_equalities<F>(diffs: &mut Vec<Diff>, eliminate: F)
+where
+    F: Fn(usize, &Change, &Change) -> bool,
+{
+    let mut segments = segments(diffs);
+    let mut modified = false;
+    let mut index = 1;
+    while index + 1 < segments.len() {
+        if let (Segment::Change(before), Segment::Equal { length, .. }, Segment::Change(after)) =
+            (segments[index - 1], segments[index], segments[index + 1])
+        {
+            if eliminate(length, &before, &after) {
+                segments[index - 1] = Segment::Change(Change {
+                    left_index: before.left_index,
+                    left_length: before.left_length + length + after.left_length,
+                    right_index: before.right_index,
+                    right_length: before.right_length + length + after.right_length,
+                });
+                segments.drain(index..=index + 1);
+                modified = true;
+                index = index.saturating_sub(2).max(1);
+                continue;
+            }
+        }
+        index += 1;
+    }
+    if modified {
+        *diffs = into_diffs(&segments);
+    }
+}
+
+/// Converts the diffs into segments, combining adjacent diffs of the same
+/// kind and dropping empty diffs.
+fn segments(diffs: &[Diff]) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::with_capacity(diffs.len());
+    let mut left_pos = 0;
+    let mut right_pos = 0;
+    for diff in diffs {
+        let (left_length, right_length) = match *diff {
+            Diff::Both { length: 0, .. }
+            | Diff::Left { length: 0, .. }
+            | Diff::Right { length: 0, .. } => continue,
+            Diff::Both {
+                left_index,
+                right_index,
+                length,
+            } => {
+                if let Some(Segment::Equal {
+                    length: last_length,
+                    ..
+                }) = segments.last_mut()
+                {
+                    *last_length += length;
+                } else {
+                    segments.push(Segment::Equal {
+                        left_index,
+                        right_index,
+                        length,
+                    });
+                }
+                left_pos = left_index + length;
+                right_pos = right_index + length;
+                continue;
+            },
+            Diff::Left { index, length } => {
+                left_pos = index;
+                (length, 0)
+            },
+            Diff::Right { index, length } => {
+                right_pos = index;
+                (0, length)
+            },
+        };
+        if let Some(Segment::Change(change)) = segments.last_mut() {
+            change.left_length += left_length;
+            change.right_length += right_length;
+        } else {
+            segments.push(Segment::Change(Change {
+                left_index: left_pos,
+                left_length,
+                right_index: right_pos,
+                right_length,
+            }));
+        }
+        left_pos += left_length;
+        right_pos += right_length;
+    }
+    segments
+}
+
+/// Converts the segments back into diffs, listing the [`Diff::Left`] before
+/// the [`Diff::Right`] of each change.
+fn into_diffs(segments: &[Segment]) -> Vec<Diff> {
+    let mut diffs = Vec::with_capacity(segments.len() + 1);
+    for segment in segments {
+        match *segment {
+            Segment::Equal {
+                left_index,
+                right_index,
+                length,
+            } => diffs.push(Diff::Both {
+                left_index,
+                right_index,
+                length,
+            }),
+            Segment::Change(change) => {
+                if change.left_length > 0 {
+                    diffs.push(Diff::Left {
+                        index: change.left_index,
+                        length: change.left_length,
+                    });
+                }
+                if change.right_length > 0 {
+                    diffs.push(Diff::Right {
+                        index: change.right_index,
+                        length: change.right_length,
+                    });
+                }
+            },
+        }
+    }
+    diffs
+}
+
+#[cfg(test)]
+mod tests;
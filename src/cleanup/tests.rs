@@ -0,0 +1,488 @@
+use super::*;
+use crate::std::vec;
+use crate::{diff, diff_str};
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use crate::apply;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn semantically_cleaned_up_diffs_still_transform_left_into_right(
+            left in prop::collection::vec(0..4_u8, 0..=50),
+            right in prop::collection::vec(0..4_u8, 0..=50),
+        ) {
+            let mut diffs = diff(&left, &right);
+
+            cleanup_semantic(&mut diffs);
+
+            prop_assert_eq!(apply(&left, &right, &diffs), Ok(right));
+        }
+
+        #[test]
+        fn efficiency_cleaned_up_diffs_still_transform_left_into_right(
+            left in prop::collection::vec(0..4_u8, 0..=50),
+            right in prop::collection::vec(0..4_u8, 0..=50),
+            edit_cost in 0..8_usize,
+        ) {
+            let mut diffs = diff(&left, &right);
+
+            cleanup_efficiency(&mut diffs, edit_cost);
+
+            prop_assert_eq!(apply(&left, &right, &diffs), Ok(right));
+        }
+    }
+}
+
+#[test]
+fn semantic_cleanup_of_diffs_of_empty_sequences() {
+    let mut diffs = diff_str("", "");
+
+    cleanup_semantic(&mut diffs);
+
+    assert_that!(diffs).contains_exactly([Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 0,
+    }]);
+}
+
+#[test]
+fn semantic_cleanup_keeps_common_subsequences_longer_than_the_changes() {
+    let mut diffs = diff_str("lorem ipsum dolor", "lorem ipsum Dolor");
+    let expected = diffs.clone();
+
+    cleanup_semantic(&mut diffs);
+
+    assert_that!(diffs).is_equal_to(expected);
+}
+
+#[test]
+fn semantic_cleanup_merges_a_short_common_subsequence_between_changes() {
+    let mut diffs = diff_str("abc d efg", "xyz d uvw");
+
+    cleanup_semantic(&mut diffs);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 9,
+        },
+        Diff::Right {
+            index: 0,
+            length: 9,
+        },
+    ]);
+}
+
+#[test]
+fn semantic_cleanup_keeps_common_prefix_and_suffix() {
+    let mut diffs = diff(&[1, 2, 3, 9, 4, 5], &[1, 6, 7, 9, 8, 5]);
+
+    cleanup_semantic(&mut diffs);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 4,
+        },
+        Diff::Right {
+            index: 1,
+            length: 4,
+        },
+        Diff::Both {
+            left_index: 5,
+            right_index: 5,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn semantic_cleanup_rechecks_the_previous_common_subsequence_after_merging() {
+    let mut diffs = vec![
+        Diff::Left {
+            index: 0,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Right {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 4,
+            right_index: 3,
+            length: 1,
+        },
+        Diff::Left {
+            index: 5,
+            length: 2,
+        },
+    ];
+
+    cleanup_semantic(&mut diffs);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 7,
+        },
+        Diff::Right {
+            index: 0,
+            length: 4,
+        },
+    ]);
+}
+
+#[test]
+fn efficiency_cleanup_merges_common_subsequence_surrounded_by_four_edits() {
+    let mut diffs = diff_str("ab12cd", "xy12zw");
+
+    cleanup_efficiency(&mut diffs, 4);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 6,
+        },
+        Diff::Right {
+            index: 0,
+            length: 6,
+        },
+    ]);
+}
+
+#[test]
+fn efficiency_cleanup_keeps_common_subsequence_not_shorter_than_the_edit_cost() {
+    let mut diffs = diff_str("ab1234cd", "xy1234zw");
+    let expected = diffs.clone();
+
+    cleanup_efficiency(&mut diffs, 4);
+
+    assert_that!(diffs).is_equal_to(expected);
+}
+
+#[test]
+fn efficiency_cleanup_merges_common_subsequence_surrounded_by_three_edits_if_shorter_than_half_the_edit_cost(
+) {
+    let mut diffs = diff_str("a1cd", "x1");
+
+    cleanup_efficiency(&mut diffs, 4);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 4,
+        },
+        Diff::Right {
+            index: 0,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn efficiency_cleanup_keeps_common_subsequence_surrounded_by_three_edits_if_not_shorter_than_half_the_edit_cost(
+) {
+    let mut diffs = diff_str("a12cd", "x12");
+    let expected = diffs.clone();
+
+    cleanup_efficiency(&mut diffs, 4);
+
+    assert_that!(diffs).is_equal_to(expected);
+}
+
+mod indent {
+    use super::*;
+    use crate::{diff_lines, Algorithm, DiffOptions, Granularity};
+
+    const LEFT: &str = "fn a() {\n    x\n}\n\nfn c() {\n    z\n}\n";
+    const RIGHT: &str = "fn a() {\n    x\n}\n\nfn b() {\n    y\n}\n\nfn c() {\n    z\n}\n";
+
+    #[cfg(feature = "std")]
+    mod properties {
+        use super::*;
+        use crate::std::{string::String, vec::Vec};
+        use crate::{apply, line_range};
+        use proptest::prelude::*;
+
+        fn text() -> impl Strategy<Value = String> {
+            prop::collection::vec(
+                prop::sample::select(vec!["}\n", "\n", "  a\n", "b\n"]),
+                0..=20,
+            )
+            .prop_map(|lines| lines.concat())
+        }
+
+        proptest! {
+            #[test]
+            fn slid_diffs_still_transform_left_into_right(
+                left in text(),
+                right in text(),
+            ) {
+                let mut diffs = diff_lines(&left, &right);
+
+                indent_heuristic(&left, &right, &mut diffs);
+
+                let left_lines: Vec<_> = left.split_inclusive('\n').collect();
+                let right_lines: Vec<_> = right.split_inclusive('\n').collect();
+                prop_assert_eq!(apply(&left_lines, &right_lines, &diffs), Ok(right_lines.clone()));
+                for diff in &diffs {
+                    if let Diff::Both { left_index, right_index, length } = *diff {
+                        let left_range = line_range(&left, left_index, length);
+                        let right_range = line_range(&right, right_index, length);
+                        prop_assert_eq!(
+                            left_range.map(|range| &left[range]),
+                            right_range.map(|range| &right[range])
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inserted_function_is_slid_down_to_start_at_its_signature() {
+        let mut diffs = vec![
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 2,
+            },
+            Diff::Right {
+                index: 2,
+                length: 4,
+            },
+            Diff::Both {
+                left_index: 2,
+                right_index: 6,
+                length: 5,
+            },
+        ];
+
+        indent_heuristic(LEFT, RIGHT, &mut diffs);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 4,
+            },
+            Diff::Right {
+                index: 4,
+                length: 4,
+            },
+            Diff::Both {
+                left_index: 4,
+                right_index: 8,
+                length: 3,
+            },
+        ]);
+    }
+
+    #[test]
+    fn removed_function_is_slid_up_to_start_at_its_signature() {
+        let mut diffs = vec![
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 2,
+            },
+            Diff::Left {
+                index: 2,
+                length: 4,
+            },
+            Diff::Both {
+                left_index: 6,
+                right_index: 2,
+                length: 5,
+            },
+        ];
+
+        indent_heuristic(RIGHT, LEFT, &mut diffs);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 4,
+            },
+            Diff::Left {
+                index: 4,
+                length: 4,
+            },
+            Diff::Both {
+                left_index: 8,
+                right_index: 4,
+                length: 3,
+            },
+        ]);
+    }
+
+    #[test]
+    fn block_that_can_not_be_slid_is_kept() {
+        let left = "lorem\nipsum\n";
+        let right = "lorem\ndolor\nipsum\n";
+        let mut diffs = diff_lines(left, right);
+        let expected = diffs.clone();
+
+        indent_heuristic(left, right, &mut diffs);
+
+        assert_that!(diffs).is_equal_to(expected);
+    }
+
+    #[test]
+    fn block_slid_to_another_block_of_the_same_text_is_merged_with_it() {
+        let left = "a\nx\nb\nb\n";
+        let right = "a\nb\n";
+        let mut diffs = vec![
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Left {
+                index: 1,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 2,
+                right_index: 1,
+                length: 1,
+            },
+            Diff::Left {
+                index: 3,
+                length: 1,
+            },
+        ];
+
+        indent_heuristic(left, right, &mut diffs);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Left {
+                index: 1,
+                length: 2,
+            },
+            Diff::Both {
+                left_index: 3,
+                right_index: 1,
+                length: 1,
+            },
+        ]);
+    }
+
+    #[test]
+    fn block_is_slid_next_to_a_block_of_the_other_text() {
+        let left = "x\nc\nc\n";
+        let right = "y\nc\nc\nc\n";
+        let mut diffs = vec![
+            Diff::Left {
+                index: 0,
+                length: 1,
+            },
+            Diff::Right {
+                index: 0,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 1,
+                right_index: 1,
+                length: 2,
+            },
+            Diff::Right {
+                index: 3,
+                length: 1,
+            },
+        ];
+
+        indent_heuristic(left, right, &mut diffs);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Left {
+                index: 0,
+                length: 1,
+            },
+            Diff::Right {
+                index: 0,
+                length: 2,
+            },
+            Diff::Both {
+                left_index: 1,
+                right_index: 2,
+                length: 2,
+            },
+        ]);
+    }
+
+    #[test]
+    fn histogram_diff_slid_by_indent_heuristic_is_the_same_as_git_diff_histogram() {
+        let left = "x\nc\nc\n";
+        let right = "y\nc\nc\nc\n";
+        let mut diffs = DiffOptions::new()
+            .algorithm(Algorithm::Histogram)
+            .granularity(Granularity::Lines)
+            .diff_str(left, right);
+
+        indent_heuristic(left, right, &mut diffs);
+
+        // git diff --histogram: @@ -1 +1,2 @@
+        assert_that!(diffs).contains_exactly([
+            Diff::Left {
+                index: 0,
+                length: 1,
+            },
+            Diff::Right {
+                index: 0,
+                length: 2,
+            },
+            Diff::Both {
+                left_index: 1,
+                right_index: 2,
+                length: 2,
+            },
+        ]);
+    }
+
+    #[test]
+    fn replaced_lines_that_can_not_be_slid_are_kept() {
+        let left = "}\n\nfn a() {\n}\n";
+        let right = "}\n\nfn b() {\n}\n\n}\n";
+        let mut diffs = diff_lines(left, right);
+        let expected = diffs.clone();
+
+        indent_heuristic(left, right, &mut diffs);
+
+        assert_that!(diffs).is_equal_to(expected);
+    }
+
+    #[test]
+    fn diffs_of_two_empty_texts_are_kept() {
+        let mut diffs = diff_lines("", "");
+
+        indent_heuristic("", "", &mut diffs);
+
+        assert_that!(diffs).contains_exactly([Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 0,
+        }]);
+    }
+}
@@ -0,0 +1,276 @@
+//! Transformation of diffs into an edit script.
+
+use crate::std::{ops::Range, slice, vec, vec::Vec};
+use crate::Diff;
+
+/// An operation of an [`EditScript`].
+///
+/// An edit script transforms the old sequence into the new sequence. The old
+/// sequence is the left sequence and the new sequence is the right sequence
+/// of the [`Diff`]s the edit script is built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditOp {
+    /// A subsequence that is equal in the old and the new sequence.
+    Equal {
+        /// The index into the old sequence where the subsequence starts.
+        old_index: usize,
+        /// The index into the new sequence where the subsequence starts.
+        new_index: usize,
+        /// The length of the subsequence.
+        length: usize,
+    },
+
+    /// A subsequence of the old sequence that is deleted.
+    Delete {
+        /// The index into the old sequence where the deleted subsequence
+        /// starts.
+        old_index: usize,
+        /// The length of the deleted subsequence.
+        old_length: usize,
+        /// The index into the new sequence where the subsequence is deleted.
+        new_index: usize,
+    },
+
+    /// A subsequence of the new sequence that is inserted.
+    Insert {
+        /// The index into the old sequence where the subsequence is
+        /// inserted.
+        old_index: usize,
+        /// The index into the new sequence where the inserted subsequence
+        /// starts.
+        new_index: usize,
+        /// The length of the inserted subsequence.
+        new_length: usize,
+    },
+
+    /// A subsequence of the old sequence that is replaced by a subsequence of
+    /// the new sequence.
+    Replace {
+        /// The index into the old sequence where the replaced subsequence
+        /// starts.
+        old_index: usize,
+        /// The length of the replaced subsequence.
+        old_length: usize,
+        /// The index into the new sequence where the replacing subsequence
+        /// starts.
+        new_index: usize,
+        /// The length of the replacing subsequence.
+        new_length: usize,
+    },
+}
+
+impl EditOp {
+    /// The range of this operation in the old sequence.
+    ///
+    /// The range is empty for an [`EditOp::Insert`].
+    #[must_use]
+    pub const fn old_range(&self) -> Range<usize> {
+        match *self {
+            Self::Equal {
+                old_index, length, ..
+            } => old_index..old_index + length,
+            Self::Delete {
+                old_index,
+                old_length,
+                ..
+            }
+            | Self::Replace {
+                old_index,
+                old_length,
+                ..
+            } => old_index..old_index + old_length,
+            Self::Insert { old_index, .. } => old_index..old_index,
+        }
+    }
+
+    /// The range of this operation in the new sequence.
+    ///
+    /// The range is empty for an [`EditOp::Delete`].
+    #[must_use]
+    pub const fn new_range(&self) -> Range<usize> {
+        match *self {
+            Self::Equal {
+                new_index, length, ..
+            } => new_index..new_index + length,
+            Self::Insert {
+                new_index,
+                new_length,
+                ..
+            }
+            | Self::Replace {
+                new_index,
+                new_length,
+                ..
+            } => new_index..new_index + new_length,
+            Self::Delete { new_index, .. } => new_index..new_index,
+        }
+    }
+}
+
+/// An edit script that transforms the old sequence into the new sequence.
+///
+/// It is built from the [`Diff`]s of two sequences. A [`Diff::Left`] is
+/// transformed into an [`EditOp::Delete`], a [`Diff::Right`] into an
+/// [`EditOp::Insert`] and a [`Diff::Both`] into an [`EditOp::Equal`]. A
+/// [`Diff::Left`] directly followed by a [`Diff::Right`] is transformed into
+/// an [`EditOp::Replace`].
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_str, EditOp, EditScript};
+///
+/// let diffs = diff_str("ABCE", "ABDE");
+///
+/// let script = EditScript::from(&diffs[..]);
+///
+/// assert_eq!(
+///     script.ops(),
+///     [
+///         EditOp::Equal { old_index: 0, new_index: 0, length: 2 },
+///         EditOp::Replace { old_index: 2, old_length: 1, new_index: 2, new_length: 1 },
+///         EditOp::Equal { old_index: 3, new_index: 3, length: 1 },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EditScript {
+    ops: Vec<EditOp>,
+}
+
+impl EditScript {
+    /// The operations of this edit script in ascending order.
+    #[must_use]
+    pub fn ops(&self) -> &[EditOp] {
+        &self.ops
+    }
+
+    /// The number of operations in this edit script.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Returns `true` if this edit script does not contain any operations.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    /// Returns `true` if this edit script contains only [`EditOp::Equal`]
+    /// operations, i.e. the old and the new sequence are equal.
+    #[must_use]
+    pub fn is_unchanged(&self) -> bool {
+        self.ops.iter().all(|op| matches!(op, EditOp::Equal { .. }))
+    }
+
+    /// Returns an iterator over the operations of this edit script.
+    pub fn iter(&self) -> slice::Iter<'_, EditOp> {
+        self.ops.iter()
+    }
+
+    /// Returns an iterator over the ranges in the old sequence of all
+    /// operations.
+    pub fn old_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.ops.iter().map(EditOp::old_range)
+    }
+
+    /// Returns an iterator over the ranges in the new sequence of all
+    /// operations.
+    pub fn new_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.ops.iter().map(EditOp::new_range)
+    }
+
+    /// Returns the operations of this edit script.
+    #[must_use]
+    pub fn into_ops(self) -> Vec<EditOp> {
+        self.ops
+    }
+}
+
+impl From<&[Diff]> for EditScript {
+    fn from(diffs: &[Diff]) -> Self {
+        let mut ops: Vec<EditOp> = Vec::with_capacity(diffs.len());
+        let mut old_index = 0;
+        let mut new_index = 0;
+        for diff in diffs {
+            match *diff {
+                Diff::Left { length: 0, .. }
+                | Diff::Right { length: 0, .. }
+                | Diff::Both { length: 0, .. } => {},
+                Diff::Left { index, length } => {
+                    old_index = index + length;
+                    ops.push(EditOp::Delete {
+                        old_index: index,
+                        old_length: length,
+                        new_index,
+                    });
+                },
+                Diff::Right { index, length } => {
+                    new_index = index + length;
+                    if let Some(&EditOp::Delete {
+                        old_index,
+                        old_length,
+                        new_index,
+                    }) = ops.last()
+                    {
+                        ops.pop();
+                        ops.push(EditOp::Replace {
+                            old_index,
+                            old_length,
+                            new_index,
+                            new_length: length,
+                        });
+                    } else {
+                        ops.push(EditOp::Insert {
+                            old_index,
+                            new_index: index,
+                            new_length: length,
+                        });
+                    }
+                },
+                Diff::Both {
+                    left_index,
+                    right_index,
+                    length,
+                } => {
+                    old_index = left_index + length;
+                    new_index = right_index + length;
+                    ops.push(EditOp::Equal {
+                        old_index: left_index,
+                        new_index: right_index,
+                        length,
+                    });
+                },
+            }
+        }
+        Self { ops }
+    }
+}
+
+impl From<&Vec<Diff>> for EditScript {
+    fn from(diffs: &Vec<Diff>) -> Self {
+        Self::from(diffs.as_slice())
+    }
+}
+
+impl IntoIterator for EditScript {
+    type Item = EditOp;
+    type IntoIter = vec::IntoIter<EditOp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EditScript {
+    type Item = &'a EditOp;
+    type IntoIter = slice::Iter<'a, EditOp>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.ops.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+use super::*;
+use crate::{diff, diff_str};
+use asserting::prelude::*;
+
+#[test]
+fn edit_script_of_two_empty_sequences_is_empty() {
+    let diffs = diff_str("", "");
+
+    let script = EditScript::from(&diffs);
+
+    assert_that!(script.is_empty()).is_true();
+    assert_that!(script.is_unchanged()).is_true();
+}
+
+#[test]
+fn edit_script_of_equal_sequences() {
+    let diffs = diff_str("lorem", "lorem");
+
+    let script = EditScript::from(&diffs);
+
+    assert_that!(script.is_unchanged()).is_true();
+    assert_that!(script.into_ops()).contains_exactly([EditOp::Equal {
+        old_index: 0,
+        new_index: 0,
+        length: 5,
+    }]);
+}
+
+#[test]
+fn left_followed_by_right_is_transformed_into_replace() {
+    let diffs = diff_str("ABCDEFG", "MNOPQ");
+
+    let script = EditScript::from(&diffs);
+
+    assert_that!(script.into_ops()).contains_exactly([EditOp::Replace {
+        old_index: 0,
+        old_length: 7,
+        new_index: 0,
+        new_length: 5,
+    }]);
+}
+
+#[test]
+fn delete_and_insert_separated_by_equal_are_not_transformed_into_replace() {
+    let diffs = diff_str("ABCD", "ABDC");
+
+    let script = EditScript::from(&diffs);
+
+    assert_that!(script.into_ops()).contains_exactly([
+        EditOp::Equal {
+            old_index: 0,
+            new_index: 0,
+            length: 2,
+        },
+        EditOp::Delete {
+            old_index: 2,
+            old_length: 1,
+            new_index: 2,
+        },
+        EditOp::Equal {
+            old_index: 3,
+            new_index: 2,
+            length: 1,
+        },
+        EditOp::Insert {
+            old_index: 4,
+            new_index: 3,
+            new_length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn edit_script_of_abcabba_and_cbabac() {
+    let diffs = diff_str("abcabba", "cbabac");
+
+    let script = EditScript::from(&diffs);
+
+    assert_that!(script.into_ops()).contains_exactly([
+        EditOp::Delete {
+            old_index: 0,
+            old_length: 2,
+            new_index: 0,
+        },
+        EditOp::Equal {
+            old_index: 2,
+            new_index: 0,
+            length: 1,
+        },
+        EditOp::Insert {
+            old_index: 3,
+            new_index: 1,
+            new_length: 1,
+        },
+        EditOp::Equal {
+            old_index: 3,
+            new_index: 2,
+            length: 2,
+        },
+        EditOp::Delete {
+            old_index: 5,
+            old_length: 1,
+            new_index: 4,
+        },
+        EditOp::Equal {
+            old_index: 6,
+            new_index: 4,
+            length: 1,
+        },
+        EditOp::Insert {
+            old_index: 7,
+            new_index: 5,
+            new_length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn old_and_new_ranges_of_an_edit_script() {
+    let diffs = diff_str("ABCE", "ABXYE");
+
+    let script = EditScript::from(&diffs);
+
+    assert_that!(script.old_ranges().collect::<Vec<_>>()).contains_exactly([0..2, 2..3, 3..4]);
+    assert_that!(script.new_ranges().collect::<Vec<_>>()).contains_exactly([0..2, 2..4, 4..5]);
+}
+
+#[test]
+fn applying_the_edit_script_transforms_the_old_into_the_new_sequence() {
+    let old = [1, 2, 3, 4, 5, 6, 7, 8];
+    let new = [0, 2, 3, 5, 6, 9, 9, 8, 10];
+    let diffs = diff(&old, &new);
+
+    let script = EditScript::from(&diffs);
+
+    let mut result = Vec::new();
+    for op in &script {
+        match op {
+            EditOp::Equal { .. } => result.extend_from_slice(&old[op.old_range()]),
+            EditOp::Delete { .. } => {},
+            EditOp::Insert { .. } | EditOp::Replace { .. } => {
+                result.extend_from_slice(&new[op.new_range()]);
+            },
+        }
+    }
+    assert_that!(result).is_equal_to(new.to_vec());
+}
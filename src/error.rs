@@ -0,0 +1,242 @@
+//! Errors that can occur when finding the differences of two sequences,
+//! when applying them or when parsing them.
+
+use crate::std::{collections::TryReserveError, fmt};
+use crate::Algorithm;
+
+/// Identifies one of the two sequences that are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The left sequence.
+    Left,
+    /// The right sequence.
+    Right,
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Left => f.write_str("left"),
+            Self::Right => f.write_str("right"),
+        }
+    }
+}
+
+/// Error returned by the fallible diff functions, like [`try_diff()`].
+///
+/// [`try_diff()`]: crate::try_diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffError {
+    /// One of the sequences is longer than the max supported length, which is
+    /// returned by [`max_sequence_length()`].
+    ///
+    /// [`max_sequence_length()`]: crate::max_sequence_length
+    SequenceTooLong {
+        /// The sequence that is too long.
+        side: Side,
+        /// The length of the sequence.
+        length: usize,
+        /// The max supported length of a sequence.
+        max_length: usize,
+    },
+
+    /// The memory needed to search the differences could not be allocated.
+    AllocationFailed(TryReserveError),
+
+    /// The algorithm looks up the elements by their order, but the elements
+    /// are only compared for equality. Such algorithms can only be used by
+    /// [`DiffOptions::diff_ord_slices()`].
+    ///
+    /// [`DiffOptions::diff_ord_slices()`]: crate::DiffOptions::diff_ord_slices
+    OrderRequired {
+        /// The algorithm that needs to order the elements.
+        algorithm: Algorithm,
+    },
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SequenceTooLong {
+                side,
+                length,
+                max_length,
+            } => write!(
+                f,
+                "the {side} sequence is longer than the max supported length of {max_length}, its length is {length}"
+            ),
+            Self::AllocationFailed(error) => {
+                write!(f, "failed to allocate memory for finding the differences: {error}")
+            },
+            Self::OrderRequired { algorithm } => write!(
+                f,
+                "the {algorithm:?} algorithm needs elements that are ordered, but they are only compared for equality"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DiffError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::SequenceTooLong { .. } | Self::OrderRequired { .. } => None,
+            Self::AllocationFailed(error) => Some(error),
+        }
+    }
+}
+
+impl From<TryReserveError> for DiffError {
+    fn from(error: TryReserveError) -> Self {
+        Self::AllocationFailed(error)
+    }
+}
+
+/// Error returned when a patch or a list of diffs can not be applied to a
+/// sequence, like by [`apply()`].
+///
+/// [`apply()`]: crate::apply
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatchError {
+    /// A diff refers to elements beyond the end of a sequence.
+    OutOfBounds {
+        /// The sequence that is too short.
+        side: Side,
+        /// The index of the first element the diff refers to.
+        index: usize,
+        /// The number of elements the diff refers to.
+        length: usize,
+        /// The length of the sequence.
+        sequence_length: usize,
+    },
+
+    /// A diff does not start where the previous diff ended.
+    UnexpectedIndex {
+        /// The sequence the index refers to.
+        side: Side,
+        /// The index of the first element the diff refers to.
+        index: usize,
+        /// The index where the previous diff ended.
+        expected: usize,
+    },
+
+    /// The diffs do not cover a sequence up to its end.
+    Incomplete {
+        /// The sequence that is not covered completely.
+        side: Side,
+        /// The number of elements covered by the diffs.
+        covered_length: usize,
+        /// The length of the sequence.
+        sequence_length: usize,
+    },
+
+    /// The elements of a sequence do not match the elements the patch
+    /// expects to replace.
+    Mismatch {
+        /// The sequence that does not match.
+        side: Side,
+        /// The index of the first element that is replaced.
+        index: usize,
+    },
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds {
+                side,
+                index,
+                length,
+                sequence_length,
+            } => write!(
+                f,
+                "the range {index}..{} is out of bounds of the {side} sequence, its length is {sequence_length}",
+                index.saturating_add(*length)
+            ),
+            Self::UnexpectedIndex {
+                side,
+                index,
+                expected,
+            } => write!(
+                f,
+                "a diff starts at index {index} of the {side} sequence, but the expected index is {expected}"
+            ),
+            Self::Incomplete {
+                side,
+                covered_length,
+                sequence_length,
+            } => write!(
+                f,
+                "the diffs cover only {covered_length} elements of the {side} sequence, its length is {sequence_length}"
+            ),
+            Self::Mismatch { side, index } => write!(
+                f,
+                "the elements at index {index} of the {side} sequence do not match the patch"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PatchError {}
+
+/// Error returned when a text in the unified diff format can not be parsed
+/// by [`parse_unified()`].
+///
+/// The line numbers are 1-based.
+///
+/// [`parse_unified()`]: crate::parse_unified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line starting with `@@` is not a valid hunk header.
+    InvalidHunkHeader {
+        /// The number of the line.
+        line: usize,
+    },
+
+    /// A line within a hunk does not start with one of ` `, `-`, `+` or `\`.
+    UnexpectedLine {
+        /// The number of the line.
+        line: usize,
+    },
+
+    /// A hunk contains less lines than announced by its header.
+    IncompleteHunk {
+        /// The number of the line with the hunk header.
+        line: usize,
+    },
+
+    /// A hunk was found before the `---` and `+++` header lines of a file.
+    MissingFileHeader {
+        /// The number of the line with the hunk header.
+        line: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHunkHeader { line } => {
+                write!(f, "invalid hunk header in line {line}")
+            },
+            Self::UnexpectedLine { line } => {
+                write!(f, "unexpected line {line} within a hunk")
+            },
+            Self::IncompleteHunk { line } => {
+                write!(
+                    f,
+                    "the hunk starting in line {line} has less lines than announced in its header"
+                )
+            },
+            Self::MissingFileHeader { line } => {
+                write!(
+                    f,
+                    "the hunk starting in line {line} is not preceded by a file header"
+                )
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
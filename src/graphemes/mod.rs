@@ -0,0 +1,56 @@
+//! Find the differences between two texts grapheme cluster by grapheme
+//! cluster.
+
+use crate::std::vec::Vec;
+use crate::{diff_tokens, try_diff_tokens, Diff, DiffError, Tokenizer};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Splits a text into extended grapheme clusters.
+///
+/// An extended grapheme cluster is what a user perceives as a single
+/// character, like a letter with combining accents or an emoji composed of
+/// several code points joined by zero width joiners (ZWJ).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GraphemeTokenizer;
+
+impl Tokenizer for GraphemeTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        text.graphemes(true).collect()
+    }
+}
+
+/// Find the common subsequences and differences between two strings
+/// comparing extended grapheme clusters.
+///
+/// Different to [`diff_str()`], which compares `char`s, a grapheme cluster
+/// composed of several `char`s is never split into different [`Diff`]s.
+///
+/// The indices and lengths of the returned [`Diff`]s count grapheme
+/// clusters. Use [`token_range()`] with the [`GraphemeTokenizer`] to get the
+/// byte range of the grapheme clusters in the original string.
+///
+/// # Panics
+///
+/// Panics if one of the strings has more grapheme clusters than the max
+/// supported length [`max_sequence_length()`] or if the memory needed to find
+/// the differences can not be allocated. See [`try_diff_graphemes()`] for a
+/// variant that returns an error instead.
+///
+/// [`diff_str()`]: crate::diff_str
+/// [`max_sequence_length()`]: crate::max_sequence_length
+/// [`token_range()`]: crate::token_range
+#[must_use]
+pub fn diff_graphemes(left: &str, right: &str) -> Vec<Diff> {
+    diff_tokens(left, right, GraphemeTokenizer)
+}
+
+/// Find the common subsequences and differences between two strings
+/// comparing extended grapheme clusters.
+///
+/// This is the fallible variant of [`diff_graphemes()`].
+pub fn try_diff_graphemes(left: &str, right: &str) -> Result<Vec<Diff>, DiffError> {
+    try_diff_tokens(left, right, GraphemeTokenizer)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,95 @@
+use super::*;
+use crate::{diff_str, token_range};
+use asserting::prelude::*;
+
+#[test]
+fn grapheme_tokenizer_splits_into_extended_grapheme_clusters() {
+    let tokens = GraphemeTokenizer.tokenize("e\u{301}a👩‍👩‍👧!");
+
+    assert_that!(tokens).contains_exactly(["e\u{301}", "a", "👩‍👩‍👧", "!"]);
+}
+
+#[test]
+fn diff_graphemes_does_not_split_a_combining_accent_from_its_base_letter() {
+    let left = "cafe";
+    let right = "cafe\u{301}";
+
+    let diffs = diff_graphemes(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 3,
+        },
+        Diff::Left {
+            index: 3,
+            length: 1,
+        },
+        Diff::Right {
+            index: 3,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn diff_str_splits_a_combining_accent_from_its_base_letter() {
+    let left = "cafe";
+    let right = "cafe\u{301}";
+
+    let diffs = diff_str(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 4,
+        },
+        Diff::Right {
+            index: 4,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn diff_graphemes_does_not_split_a_zwj_emoji_sequence() {
+    let left = "family: 👩‍👩‍👧";
+    let right = "family: 👩‍👩‍👦";
+
+    let diffs = diff_graphemes(left, right);
+
+    assert_that!(diffs.clone()).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 8,
+        },
+        Diff::Left {
+            index: 8,
+            length: 1,
+        },
+        Diff::Right {
+            index: 8,
+            length: 1,
+        },
+    ]);
+
+    let Diff::Right { index, length } = diffs[2] else {
+        panic!("expected a diff of type `Diff::Right`");
+    };
+    let range =
+        token_range(right, GraphemeTokenizer, index, length).unwrap_or_else(|| panic!("invalid"));
+    assert_that!(&right[range]).is_equal_to("👩‍👩‍👦");
+}
+
+#[test]
+fn try_diff_graphemes_returns_the_same_diffs_as_diff_graphemes() {
+    let left = "nai\u{308}ve";
+    let right = "naive";
+
+    let diffs = try_diff_graphemes(left, right);
+
+    assert_that!(diffs).has_value(diff_graphemes(left, right));
+}
@@ -0,0 +1,309 @@
+//! Histogram diff algorithm.
+//!
+//! This is a port of the histogram diff of git, which was derived from the
+//! one of `JGit`. It extends the patience diff: instead of anchoring on
+//! elements that are unique in both sequences, it anchors on the longest
+//! common subsequence that starts with an element that occurs least often in
+//! the left sequence. The parts before and after the anchor are diffed
+//! recursively.
+//!
+//! Elements that occur more than [`MAX_OCCURRENCES`] times in the left
+//! sequence are not used as anchors. If no anchor can be found although the
+//! two sequences have elements in common, the Myers algorithm is used.
+
+use crate::myers::{self, DiffList};
+use crate::std::{
+    collections::{btree_map::Entry, BTreeMap},
+    ops::Range,
+    vec,
+    vec::Vec,
+};
+use crate::{Capture, Diff, DiffError, Limits};
+
+/// Elements that occur more often than this in the left sequence are not
+/// used as anchors.
+const MAX_OCCURRENCES: usize = 64;
+
+/// Find the common subsequences and differences between two slices using
+/// the histogram diff algorithm.
+///
+/// The limits apply to the parts that are diffed using the Myers
+/// algorithm, see [`Limits`].
+///
+/// Returns an error if the memory needed for the search can not be
+/// allocated.
+pub fn diff<T>(left: &[T], right: &[T], limits: &mut Limits<'_>) -> Result<Vec<Diff>, DiffError>
+where
+    T: Ord,
+{
+    if left.len() + right.len() == 0 {
+        return Ok(vec![Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 0,
+        }]);
+    }
+
+    let mut histogram = Histogram {
+        left,
+        right,
+        limits,
+        diffs: DiffList::default(),
+    };
+    histogram.diff_range(0..left.len(), 0..right.len())?;
+    Ok(histogram.diffs.into_vec())
+}
+
+/// State of the recursive search.
+struct Histogram<'a, 'l, 'd, T> {
+    left: &'a [T],
+    right: &'a [T],
+    limits: &'l mut Limits<'d>,
+    diffs: DiffList<Capture>,
+}
+
+impl<T> Histogram<'_, '_, '_, T>
+where
+    T: Ord,
+{
+    /// Lists the diffs of the subsequences `left[left_range]` and
+    /// `right[right_range]`.
+    fn diff_range(
+        &mut self,
+        mut left_range: Range<usize>,
+        mut right_range: Range<usize>,
+    ) -> Result<(), DiffError> {
+        loop {
+            if left_range.is_empty() {
+                self.diffs.push_right(right_range.start, right_range.len());
+                return Ok(());
+            }
+            if right_range.is_empty() {
+                self.diffs.push_left(left_range.start, left_range.len());
+                return Ok(());
+            }
+
+            match Anchor::find(
+                self.left,
+                self.right,
+                left_range.clone(),
+                right_range.clone(),
+            ) {
+                Anchor::Found {
+                    left: left_anchor,
+                    right: right_anchor,
+                } => {
+                    self.diff_range(
+                        left_range.start..left_anchor.start,
+                        right_range.start..right_anchor.start,
+                    )?;
+                    self.diffs
+                        .push_both(left_anchor.start, right_anchor.start, left_anchor.len());
+                    left_range.start = left_anchor.end;
+                    right_range.start = right_anchor.end;
+                },
+                Anchor::NothingInCommon => {
+                    self.diffs.push_left(left_range.start, left_range.len());
+                    self.diffs.push_right(right_range.start, right_range.len());
+                    return Ok(());
+                },
+                Anchor::TooManyOccurrences => {
+                    let diffs = myers::diff_within_limits(
+                        &self.left[left_range.clone()],
+                        &self.right[right_range.clone()],
+                        PartialEq::eq,
+                        self.limits,
+                    )?;
+                    self.diffs
+                        .extend(&diffs, left_range.start, right_range.start);
+                    return Ok(());
+                },
+            }
+        }
+    }
+}
+
+/// The result of searching an anchor within two subsequences.
+enum Anchor {
+    /// The common subsequence to anchor on, given by its ranges in the left
+    /// and in the right sequence.
+    Found {
+        left: Range<usize>,
+        right: Range<usize>,
+    },
+    /// The two subsequences have no element in common.
+    NothingInCommon,
+    /// All common elements occur too often in the left subsequence.
+    TooManyOccurrences,
+}
+
+/// Occurrences of an element in the left subsequence.
+struct Occurrences {
+    /// The index of the first occurrence.
+    first: usize,
+    /// The number of occurrences.
+    count: usize,
+}
+
+/// Index of the elements of the left subsequence, which is searched for the
+/// anchor.
+struct HistogramIndex<'a, T> {
+    left: &'a [T],
+    right: &'a [T],
+    left_range: Range<usize>,
+    right_range: Range<usize>,
+    occurrences: BTreeMap<&'a T, Occurrences>,
+    /// The index of the next occurrence of the element at each index of the
+    /// left subsequence.
+    next: Vec<Option<usize>>,
+    /// The lowest number of occurrences of the elements of the anchor found
+    /// so far.
+    count: usize,
+    has_common: bool,
+    anchor: Option<(Range<usize>, Range<usize>)>,
+}
+
+impl Anchor {
+    /// Searches the anchor within `left[left_range]` and
+    /// `right[right_range]`, which must not be empty.
+    fn find<T>(left: &[T], right: &[T], left_range: Range<usize>, right_range: Range<usize>) -> Self
+    where
+        T: Ord,
+    {
+        let mut index = HistogramIndex {
+            left,
+            right,
+            next: vec![None; left_range.len()],
+            left_range,
+            right_range,
+            occurrences: BTreeMap::new(),
+            count: MAX_OCCURRENCES + 1,
+            has_common: false,
+            anchor: None,
+        };
+        index.scan_left();
+
+        let mut right_index = index.right_range.start;
+        while right_index < index.right_range.end {
+            right_index = index.try_anchor_at(right_index);
+        }
+
+        match index.anchor {
+            _ if index.has_common && index.count > MAX_OCCURRENCES => Self::TooManyOccurrences,
+            Some((left, right)) => Self::Found { left, right },
+            None => Self::NothingInCommon,
+        }
+    }
+}
+
+impl<T> HistogramIndex<'_, T>
+where
+    T: Ord,
+{
+    /// Records the occurrences of the elements of the left subsequence.
+    fn scan_left(&mut self) {
+        for index in self.left_range.clone().rev() {
+            match self.occurrences.entry(&self.left[index]) {
+                Entry::Occupied(mut entry) => {
+                    let occurrences = entry.get_mut();
+                    self.next[index - self.left_range.start] = Some(occurrences.first);
+                    occurrences.first = index;
+                    occurrences.count += 1;
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(Occurrences {
+                        first: index,
+                        count: 1,
+                    });
+                },
+            }
+        }
+    }
+
+    /// Number of occurrences of the element at the given index of the left
+    /// sequence.
+    fn count_at(&self, left_index: usize) -> usize {
+        self.occurrences
+            .get(&self.left[left_index])
+            .map_or(0, |occurrences| occurrences.count)
+    }
+
+    /// Index of the next occurrence of the element at the given index of the
+    /// left sequence.
+    fn next_at(&self, left_index: usize) -> Option<usize> {
+        self.next[left_index - self.left_range.start]
+    }
+
+    /// Tries each occurrence of the element at `right_index` in the left
+    /// subsequence as the start of the anchor.
+    ///
+    /// Returns the index in the right sequence to continue with.
+    fn try_anchor_at(&mut self, right_index: usize) -> usize {
+        let mut right_next = right_index + 1;
+        let Some(occurrences) = self.occurrences.get(&self.right[right_index]) else {
+            return right_next;
+        };
+        self.has_common = true;
+        if occurrences.count > self.count {
+            return right_next;
+        }
+        let count = occurrences.count;
+        let mut occurrence = occurrences.first;
+
+        loop {
+            let mut left_start = occurrence;
+            let mut right_start = right_index;
+            let mut left_end = occurrence + 1;
+            let mut right_end = right_index + 1;
+            let mut anchor_count = count;
+
+            while self.left_range.start < left_start
+                && self.right_range.start < right_start
+                && self.left[left_start - 1] == self.right[right_start - 1]
+            {
+                left_start -= 1;
+                right_start -= 1;
+                if anchor_count > 1 {
+                    anchor_count = anchor_count.min(self.count_at(left_start));
+                }
+            }
+            while left_end < self.left_range.end
+                && right_end < self.right_range.end
+                && self.left[left_end] == self.right[right_end]
+            {
+                if anchor_count > 1 {
+                    anchor_count = anchor_count.min(self.count_at(left_end));
+                }
+                left_end += 1;
+                right_end += 1;
+            }
+
+            right_next = right_next.max(right_end);
+            let is_longer = self
+                .anchor
+                .as_ref()
+                .map_or(true, |(left, _)| left.len() < left_end - left_start);
+            if is_longer || anchor_count < self.count {
+                self.anchor = Some((left_start..left_end, right_start..right_end));
+                self.count = anchor_count;
+            }
+
+            // Continue with the next occurrence that is not part of the
+            // common subsequence just found.
+            let mut next = self.next_at(occurrence);
+            while let Some(index) = next {
+                if index >= left_end {
+                    break;
+                }
+                next = self.next_at(index);
+            }
+            match next {
+                Some(index) => occurrence = index,
+                None => return right_next,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
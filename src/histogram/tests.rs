@@ -0,0 +1,124 @@
+use super::*;
+use crate::diff as diff_myers;
+use crate::std::iter;
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use crate::assertions::assert_diffs_transform;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn histogram_diffs_transform_left_into_right(
+            left in prop::collection::vec(0..8_u8, 0..=100),
+            right in prop::collection::vec(0..8_u8, 0..=100),
+        ) {
+            let diffs = diff(&left, &right, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}"));
+
+            assert_diffs_transform(&left, &right, &diffs)?;
+        }
+    }
+}
+
+#[test]
+fn histogram_diff_of_two_empty_sequences() {
+    let left: [u8; 0] = [];
+    let right: [u8; 0] = [];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 0,
+    }]);
+}
+
+#[test]
+fn histogram_diff_of_sequences_with_nothing_in_common() {
+    let left = ["a", "b"];
+    let right = ["c", "d", "e"];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Left {
+            index: 0,
+            length: 2,
+        },
+        Diff::Right {
+            index: 0,
+            length: 3,
+        },
+    ]);
+}
+
+#[test]
+fn histogram_diff_anchors_on_the_longest_subsequence_around_the_least_frequent_elements() {
+    let left = ["}", "fn a() {", "}", "fn b() {", "}"];
+    let right = ["fn b() {", "}", "fn a() {", "}", "}"];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 0,
+            right_index: 1,
+            length: 3,
+        },
+        Diff::Left {
+            index: 3,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 4,
+            right_index: 4,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn histogram_diff_does_not_anchor_on_frequent_elements_if_a_rare_one_is_common() {
+    let left = ["x", "x", "x", "y", "x"];
+    let right = ["x", "y", "x", "x"];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Left {
+            index: 0,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 0,
+            length: 3,
+        },
+        Diff::Right {
+            index: 3,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn histogram_diff_falls_back_to_myers_if_all_common_elements_occur_too_often() {
+    let left = iter::repeat('a')
+        .take(MAX_OCCURRENCES + 1)
+        .chain(iter::once('b'))
+        .collect::<Vec<_>>();
+    let right = iter::once('c')
+        .chain(iter::repeat('a').take(MAX_OCCURRENCES + 1))
+        .collect::<Vec<_>>();
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(diff_myers(&left, &right));
+}
@@ -0,0 +1,280 @@
+//! Consume the differences of two sequences while they are found, without
+//! collecting them first.
+
+use crate::std::vec::Vec;
+use crate::Diff;
+
+/// Receives the common subsequences and differences of two sequences in
+/// ascending order while they are found.
+///
+/// The callbacks correspond to the kinds of [`Diff`]: a common subsequence
+/// is passed to [`equal()`](Self::equal), a subsequence only present in the
+/// left sequence to [`delete()`](Self::delete) and one only present in the
+/// right sequence to [`insert()`](Self::insert). Adjacent subsequences of the
+/// same kind are merged, no subsequence is empty and within a run of
+/// differences the deletion is always passed before the insertion. After the
+/// last subsequence, [`finish()`](Self::finish) is called.
+///
+/// [`replace()`](Self::replace) is only called by adapters like [`Replace`],
+/// which merge a deletion and the following insertion.
+///
+/// All callbacks do nothing by default, so only the ones of interest need to
+/// be implemented.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_with_hook, DiffHook};
+///
+/// #[derive(Default)]
+/// struct Stats {
+///     deleted: usize,
+///     inserted: usize,
+/// }
+///
+/// impl DiffHook for Stats {
+///     fn delete(&mut self, _index: usize, length: usize) {
+///         self.deleted += length;
+///     }
+///
+///     fn insert(&mut self, _index: usize, length: usize) {
+///         self.inserted += length;
+///     }
+/// }
+///
+/// let mut stats = Stats::default();
+///
+/// diff_with_hook(&[1, 2, 3, 4], &[1, 5, 6, 3], &mut stats);
+///
+/// assert_eq!(stats.deleted, 2);
+/// assert_eq!(stats.inserted, 2);
+/// ```
+#[allow(unused_variables)]
+pub trait DiffHook {
+    /// Receives a common subsequence that starts at `left_index` in the left
+    /// sequence and at `right_index` in the right sequence.
+    fn equal(&mut self, left_index: usize, right_index: usize, length: usize) {}
+
+    /// Receives a subsequence that is only present in the left sequence and
+    /// starts at `index`.
+    fn delete(&mut self, index: usize, length: usize) {}
+
+    /// Receives a subsequence that is only present in the right sequence and
+    /// starts at `index`.
+    fn insert(&mut self, index: usize, length: usize) {}
+
+    /// Receives a subsequence of the left sequence that is replaced by a
+    /// subsequence of the right sequence.
+    ///
+    /// By default, the replacement is passed on as a deletion followed by an
+    /// insertion.
+    fn replace(
+        &mut self,
+        left_index: usize,
+        left_length: usize,
+        right_index: usize,
+        right_length: usize,
+    ) {
+        self.delete(left_index, left_length);
+        self.insert(right_index, right_length);
+    }
+
+    /// Called once after all subsequences have been passed.
+    fn finish(&mut self) {}
+}
+
+impl<H> DiffHook for &mut H
+where
+    H: DiffHook + ?Sized,
+{
+    fn equal(&mut self, left_index: usize, right_index: usize, length: usize) {
+        (**self).equal(left_index, right_index, length);
+    }
+
+    fn delete(&mut self, index: usize, length: usize) {
+        (**self).delete(index, length);
+    }
+
+    fn insert(&mut self, index: usize, length: usize) {
+        (**self).insert(index, length);
+    }
+
+    fn replace(
+        &mut self,
+        left_index: usize,
+        left_length: usize,
+        right_index: usize,
+        right_length: usize,
+    ) {
+        (**self).replace(left_index, left_length, right_index, right_length);
+    }
+
+    fn finish(&mut self) {
+        (**self).finish();
+    }
+}
+
+/// A [`DiffHook`] that collects the subsequences as [`Diff`]s.
+///
+/// The collected diffs are the same as the ones returned by [`diff()`] for
+/// the same sequences, except that nothing is collected for two empty
+/// sequences.
+///
+/// [`diff()`]: crate::diff
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Capture {
+    diffs: Vec<Diff>,
+}
+
+impl Capture {
+    /// Creates a hook that has not collected any diffs yet.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { diffs: Vec::new() }
+    }
+
+    /// Returns the diffs collected so far.
+    #[must_use]
+    pub fn diffs(&self) -> &[Diff] {
+        &self.diffs
+    }
+
+    /// Returns the collected diffs.
+    #[must_use]
+    pub fn into_diffs(self) -> Vec<Diff> {
+        self.diffs
+    }
+}
+
+impl DiffHook for Capture {
+    fn equal(&mut self, left_index: usize, right_index: usize, length: usize) {
+        self.diffs.push(Diff::Both {
+            left_index,
+            right_index,
+            length,
+        });
+    }
+
+    fn delete(&mut self, index: usize, length: usize) {
+        self.diffs.push(Diff::Left { index, length });
+    }
+
+    fn insert(&mut self, index: usize, length: usize) {
+        self.diffs.push(Diff::Right { index, length });
+    }
+}
+
+/// A [`DiffHook`] adapter that merges a deletion and the insertion following
+/// it into a replacement.
+///
+/// A run of differences is passed on to [`DiffHook::replace()`] of the
+/// wrapped hook if it contains both, a deletion and an insertion, and to
+/// [`DiffHook::delete()`] or [`DiffHook::insert()`] otherwise.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_with_hook, DiffHook, Replace};
+///
+/// #[derive(Default)]
+/// struct Replacements(Vec<(usize, usize)>);
+///
+/// impl DiffHook for Replacements {
+///     fn replace(&mut self, left_index: usize, _: usize, right_index: usize, _: usize) {
+///         self.0.push((left_index, right_index));
+///     }
+/// }
+///
+/// let mut hook = Replace::new(Replacements::default());
+///
+/// diff_with_hook(&[1, 2, 3, 4], &[1, 5, 3, 6], &mut hook);
+///
+/// assert_eq!(hook.into_inner().0, [(1, 1), (3, 3)]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Replace<H> {
+    hook: H,
+    delete: Option<(usize, usize)>,
+    insert: Option<(usize, usize)>,
+}
+
+impl<H> Replace<H>
+where
+    H: DiffHook,
+{
+    /// Wraps the given hook.
+    #[must_use]
+    pub const fn new(hook: H) -> Self {
+        Self {
+            hook,
+            delete: None,
+            insert: None,
+        }
+    }
+
+    /// Returns the wrapped hook.
+    #[must_use]
+    pub fn into_inner(self) -> H {
+        self.hook
+    }
+
+    /// Passes on the pending run of differences.
+    fn flush(&mut self) {
+        match (self.delete.take(), self.insert.take()) {
+            (Some((left_index, left_length)), Some((right_index, right_length))) => {
+                self.hook
+                    .replace(left_index, left_length, right_index, right_length);
+            },
+            (Some((index, length)), None) => self.hook.delete(index, length),
+            (None, Some((index, length))) => self.hook.insert(index, length),
+            (None, None) => {},
+        }
+    }
+}
+
+impl<H> DiffHook for Replace<H>
+where
+    H: DiffHook,
+{
+    fn equal(&mut self, left_index: usize, right_index: usize, length: usize) {
+        self.flush();
+        self.hook.equal(left_index, right_index, length);
+    }
+
+    fn delete(&mut self, index: usize, length: usize) {
+        if self.insert.is_some() {
+            self.flush();
+        }
+        match &mut self.delete {
+            Some((_, pending_length)) => *pending_length += length,
+            None => self.delete = Some((index, length)),
+        }
+    }
+
+    fn insert(&mut self, index: usize, length: usize) {
+        match &mut self.insert {
+            Some((_, pending_length)) => *pending_length += length,
+            None => self.insert = Some((index, length)),
+        }
+    }
+
+    fn replace(
+        &mut self,
+        left_index: usize,
+        left_length: usize,
+        right_index: usize,
+        right_length: usize,
+    ) {
+        self.flush();
+        self.hook
+            .replace(left_index, left_length, right_index, right_length);
+    }
+
+    fn finish(&mut self) {
+        self.flush();
+        self.hook.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests;
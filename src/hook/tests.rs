@@ -0,0 +1,211 @@
+use super::*;
+use crate::std::vec;
+use crate::{diff, diff_with_hook, max_sequence_length, try_diff_with_hook, DiffError, Side};
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn the_captured_diffs_are_the_same_as_the_listed_diffs(
+            left in prop::collection::vec(0..8_u8, 1..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+        ) {
+            let mut capture = Capture::new();
+
+            diff_with_hook(&left, &right, &mut capture);
+
+            prop_assert_eq!(capture.into_diffs(), diff(&left, &right));
+        }
+
+        #[test]
+        fn the_replacements_cover_the_same_runs_as_deletions_and_insertions(
+            left in prop::collection::vec(0..8_u8, 0..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+        ) {
+            let mut replace = Replace::new(Capture::new());
+
+            diff_with_hook(&left, &right, &mut replace);
+
+            let mut capture = Capture::new();
+            diff_with_hook(&left, &right, &mut capture);
+            prop_assert_eq!(replace.into_inner(), capture);
+        }
+    }
+}
+
+/// The calls of the hook methods in the order they were made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Call {
+    Equal(usize, usize, usize),
+    Delete(usize, usize),
+    Insert(usize, usize),
+    Replace(usize, usize, usize, usize),
+    Finish,
+}
+
+#[derive(Debug, Default)]
+struct Recorder {
+    calls: Vec<Call>,
+}
+
+impl DiffHook for Recorder {
+    fn equal(&mut self, left_index: usize, right_index: usize, length: usize) {
+        self.calls
+            .push(Call::Equal(left_index, right_index, length));
+    }
+
+    fn delete(&mut self, index: usize, length: usize) {
+        self.calls.push(Call::Delete(index, length));
+    }
+
+    fn insert(&mut self, index: usize, length: usize) {
+        self.calls.push(Call::Insert(index, length));
+    }
+
+    fn replace(
+        &mut self,
+        left_index: usize,
+        left_length: usize,
+        right_index: usize,
+        right_length: usize,
+    ) {
+        self.calls.push(Call::Replace(
+            left_index,
+            left_length,
+            right_index,
+            right_length,
+        ));
+    }
+
+    fn finish(&mut self) {
+        self.calls.push(Call::Finish);
+    }
+}
+
+#[test]
+fn diff_with_hook_passes_the_subsequences_in_ascending_order_and_finishes() {
+    let left = "lorem ipsum dolor".chars().collect::<Vec<_>>();
+    let right = "lorem dolores sit".chars().collect::<Vec<_>>();
+    let mut recorder = Recorder::default();
+
+    diff_with_hook(&left, &right, &mut recorder);
+
+    assert_that!(recorder.calls).contains_exactly([
+        Call::Equal(0, 0, 6),
+        Call::Delete(6, 6),
+        Call::Equal(12, 6, 5),
+        Call::Insert(11, 6),
+        Call::Finish,
+    ]);
+}
+
+#[test]
+fn diff_with_hook_passes_the_deletion_before_the_insertion_of_a_run() {
+    let left = [1, 2, 3];
+    let right = [1, 4, 3];
+    let mut recorder = Recorder::default();
+
+    diff_with_hook(&left, &right, &mut recorder);
+
+    assert_that!(recorder.calls).contains_exactly([
+        Call::Equal(0, 0, 1),
+        Call::Delete(1, 1),
+        Call::Insert(1, 1),
+        Call::Equal(2, 2, 1),
+        Call::Finish,
+    ]);
+}
+
+#[test]
+fn diff_with_hook_of_two_empty_sequences_only_finishes() {
+    let left: [u8; 0] = [];
+    let right: [u8; 0] = [];
+    let mut recorder = Recorder::default();
+
+    diff_with_hook(&left, &right, &mut recorder);
+
+    assert_that!(recorder.calls).contains_exactly([Call::Finish]);
+}
+
+#[test]
+fn replace_merges_a_deletion_and_the_following_insertion() {
+    let left = [1, 2, 3, 4, 5];
+    let right = [1, 6, 7, 4, 8];
+    let mut replace = Replace::new(Recorder::default());
+
+    diff_with_hook(&left, &right, &mut replace);
+
+    assert_that!(replace.into_inner().calls).contains_exactly([
+        Call::Equal(0, 0, 1),
+        Call::Replace(1, 2, 1, 2),
+        Call::Equal(3, 3, 1),
+        Call::Replace(4, 1, 4, 1),
+        Call::Finish,
+    ]);
+}
+
+#[test]
+fn replace_passes_on_a_deletion_or_an_insertion_without_counterpart() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 3, 4, 5];
+    let mut replace = Replace::new(Recorder::default());
+
+    diff_with_hook(&left, &right, &mut replace);
+
+    assert_that!(replace.into_inner().calls).contains_exactly([
+        Call::Equal(0, 0, 1),
+        Call::Delete(1, 1),
+        Call::Equal(2, 1, 2),
+        Call::Insert(3, 1),
+        Call::Finish,
+    ]);
+}
+
+#[test]
+fn the_default_replace_passes_on_a_deletion_and_an_insertion() {
+    let mut capture = Capture::new();
+
+    capture.replace(2, 3, 4, 5);
+
+    assert_that!(capture.diffs().to_vec()).contains_exactly([
+        Diff::Left {
+            index: 2,
+            length: 3,
+        },
+        Diff::Right {
+            index: 4,
+            length: 5,
+        },
+    ]);
+}
+
+#[test]
+fn capture_collects_the_same_diffs_as_listed_by_diff() {
+    let left = [1, 2, 3, 4, 5, 6];
+    let right = [1, 3, 2, 4, 6, 7];
+    let mut capture = Capture::new();
+
+    diff_with_hook(&left, &right, &mut capture);
+
+    assert_that!(capture.into_diffs()).is_equal_to(diff(&left, &right));
+}
+
+#[test]
+fn try_diff_with_hook_of_too_long_sequences_returns_an_error_without_calling_the_hook() {
+    let left = vec![(); 2];
+    let right = vec![(); max_sequence_length() + 1];
+    let mut recorder = Recorder::default();
+
+    let result = try_diff_with_hook(&left, &right, &mut recorder);
+
+    assert_that!(result).has_error(DiffError::SequenceTooLong {
+        side: Side::Right,
+        length: max_sequence_length() + 1,
+        max_length: max_sequence_length(),
+    });
+    assert_that!(recorder.calls).is_empty();
+}
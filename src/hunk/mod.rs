@@ -0,0 +1,186 @@
+//! Grouping of diffs into hunks of changes with surrounding context.
+
+use crate::std::{ops::Range, vec::Vec};
+use crate::Diff;
+
+/// A group of changes with up to a given number of common elements as
+/// context before and after the changes.
+///
+/// Hunks are created by [`group_hunks()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    left_start: usize,
+    left_len: usize,
+    right_start: usize,
+    right_len: usize,
+    diffs: Vec<Diff>,
+}
+
+impl Hunk {
+    const fn new(left_start: usize, right_start: usize) -> Self {
+        Self {
+            left_start,
+            left_len: 0,
+            right_start,
+            right_len: 0,
+            diffs: Vec::new(),
+        }
+    }
+
+    /// The range of the left sequence that is covered by this hunk.
+    ///
+    /// The range is empty if the hunk only inserts elements. In this case
+    /// the range starts at the index in the left sequence where the elements
+    /// are inserted.
+    #[must_use]
+    pub const fn left_range(&self) -> Range<usize> {
+        self.left_start..self.left_start + self.left_len
+    }
+
+    /// The range of the right sequence that is covered by this hunk.
+    ///
+    /// The range is empty if the hunk only removes elements. In this case
+    /// the range starts at the index in the right sequence where the elements
+    /// are removed.
+    #[must_use]
+    pub const fn right_range(&self) -> Range<usize> {
+        self.right_start..self.right_start + self.right_len
+    }
+
+    /// The diffs of this hunk including the context.
+    ///
+    /// The first and the last diff are [`Diff::Both`] containing the context
+    /// unless the context is empty.
+    #[must_use]
+    pub fn diffs(&self) -> &[Diff] {
+        &self.diffs
+    }
+
+    /// Returns the diffs of this hunk including the context.
+    #[must_use]
+    pub fn into_diffs(self) -> Vec<Diff> {
+        self.diffs
+    }
+
+    fn push(&mut self, diff: Diff) {
+        match diff {
+            Diff::Left { length: 0, .. }
+            | Diff::Right { length: 0, .. }
+            | Diff::Both { length: 0, .. } => return,
+            Diff::Left { length, .. } => self.left_len += length,
+            Diff::Both { length, .. } => {
+                self.left_len += length;
+                self.right_len += length;
+            },
+            Diff::Right { length, .. } => self.right_len += length,
+        }
+        self.diffs.push(diff);
+    }
+}
+
+/// Groups the diffs into hunks of changes with up to `context` common
+/// elements before and after the changes.
+///
+/// Each hunk contains one or more changes, which are [`Diff::Left`]s and
+/// [`Diff::Right`]s, and up to `context` elements of the surrounding
+/// [`Diff::Both`]s. Two changes are grouped into the same hunk if they are
+/// separated by not more than `2 * context` common elements, so that the
+/// contexts of two hunks never overlap or touch.
+///
+/// The diffs must be in ascending order as returned by [`diff()`]. If the
+/// diffs do not contain any changes, no hunks are returned.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_str, group_hunks};
+///
+/// let diffs = diff_str("lorem ipsum dolor sit", "Lorem ipsum dolor sit!");
+///
+/// let hunks = group_hunks(&diffs, 2);
+///
+/// assert_eq!(hunks.len(), 2);
+/// assert_eq!(hunks[0].left_range(), 0..3);
+/// assert_eq!(hunks[1].left_range(), 19..21);
+/// assert_eq!(hunks[1].right_range(), 19..22);
+/// ```
+///
+/// [`diff()`]: crate::diff
+#[must_use]
+pub fn group_hunks(diffs: &[Diff], context: usize) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+    let mut leading_context = None;
+    let mut left_pos = 0;
+    let mut right_pos = 0;
+
+    for (position, diff) in diffs.iter().enumerate() {
+        match *diff {
+            Diff::Both {
+                left_index,
+                right_index,
+                length,
+            } => {
+                let is_last = position + 1 == diffs.len();
+                let context_len = length.min(context);
+                if let Some(mut hunk) = current.take() {
+                    if is_last || length > 2 * context {
+                        hunk.push(Diff::Both {
+                            left_index,
+                            right_index,
+                            length: context_len,
+                        });
+                        hunks.push(hunk);
+                    } else {
+                        hunk.push(*diff);
+                        current = Some(hunk);
+                    }
+                }
+                leading_context = Some(Diff::Both {
+                    left_index: left_index + length - context_len,
+                    right_index: right_index + length - context_len,
+                    length: context_len,
+                });
+                left_pos = left_index + length;
+                right_pos = right_index + length;
+            },
+            Diff::Left { length: 0, .. } | Diff::Right { length: 0, .. } => {},
+            Diff::Left { index, length } => {
+                let hunk = current
+                    .get_or_insert_with(|| start_hunk(&mut leading_context, index, right_pos));
+                hunk.push(*diff);
+                left_pos = index + length;
+            },
+            Diff::Right { index, length } => {
+                let hunk = current
+                    .get_or_insert_with(|| start_hunk(&mut leading_context, left_pos, index));
+                hunk.push(*diff);
+                right_pos = index + length;
+            },
+        }
+    }
+
+    hunks.extend(current);
+    hunks
+}
+
+/// Starts a new hunk at the given positions preceded by the leading context.
+fn start_hunk(leading_context: &mut Option<Diff>, left_pos: usize, right_pos: usize) -> Hunk {
+    match leading_context.take() {
+        Some(
+            context @ Diff::Both {
+                left_index,
+                right_index,
+                ..
+            },
+        ) => {
+            let mut hunk = Hunk::new(left_index, right_index);
+            hunk.push(context);
+            hunk
+        },
+        _ => Hunk::new(left_pos, right_pos),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,126 @@
+use super::*;
+use crate::{diff, diff_str};
+use asserting::prelude::*;
+
+#[test]
+fn no_hunks_for_equal_sequences() {
+    let diffs = diff_str("lorem ipsum", "lorem ipsum");
+
+    let hunks = group_hunks(&diffs, 3);
+
+    assert_that!(hunks).is_empty();
+}
+
+#[test]
+fn no_hunks_for_empty_sequences() {
+    let diffs = diff_str("", "");
+
+    let hunks = group_hunks(&diffs, 3);
+
+    assert_that!(hunks).is_empty();
+}
+
+#[test]
+fn one_hunk_with_context_before_and_after_the_change() {
+    let left = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let right = [1, 2, 3, 4, 0, 6, 7, 8, 9];
+    let diffs = diff(&left, &right);
+
+    let hunks = group_hunks(&diffs, 2);
+
+    assert_that!(hunks.len()).is_equal_to(1);
+    let hunk = &hunks[0];
+    assert_that!(hunk.left_range()).is_equal_to(2..7);
+    assert_that!(hunk.right_range()).is_equal_to(2..7);
+    assert_that!(hunk.diffs()).is_equal_to(
+        &[
+            Diff::Both {
+                left_index: 2,
+                right_index: 2,
+                length: 2,
+            },
+            Diff::Left {
+                index: 4,
+                length: 1,
+            },
+            Diff::Right {
+                index: 4,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 5,
+                right_index: 5,
+                length: 2,
+            },
+        ][..],
+    );
+}
+
+#[test]
+fn context_is_limited_by_the_start_and_end_of_the_sequences() {
+    let left = [1, 2, 3];
+    let right = [0, 2, 4];
+    let diffs = diff(&left, &right);
+
+    let hunks = group_hunks(&diffs, 5);
+
+    assert_that!(hunks.len()).is_equal_to(1);
+    assert_that!(hunks[0].left_range()).is_equal_to(0..3);
+    assert_that!(hunks[0].right_range()).is_equal_to(0..3);
+    assert_that!(hunks[0].clone().into_diffs()).is_equal_to(diffs);
+}
+
+#[test]
+fn changes_separated_by_more_than_twice_the_context_are_split_into_hunks() {
+    let left = [1, 2, 3, 4, 5, 6, 7, 8];
+    let right = [0, 2, 3, 4, 5, 6, 7, 9];
+    let diffs = diff(&left, &right);
+
+    let hunks = group_hunks(&diffs, 2);
+
+    assert_that!(hunks.len()).is_equal_to(2);
+    assert_that!(hunks[0].left_range()).is_equal_to(0..3);
+    assert_that!(hunks[0].right_range()).is_equal_to(0..3);
+    assert_that!(hunks[1].left_range()).is_equal_to(5..8);
+    assert_that!(hunks[1].right_range()).is_equal_to(5..8);
+}
+
+#[test]
+fn changes_separated_by_twice_the_context_are_merged_into_one_hunk() {
+    let left = [1, 2, 3, 4, 5, 6];
+    let right = [0, 2, 3, 4, 5, 9];
+    let diffs = diff(&left, &right);
+
+    let hunks = group_hunks(&diffs, 2);
+
+    assert_that!(hunks.len()).is_equal_to(1);
+    assert_that!(hunks[0].left_range()).is_equal_to(0..6);
+    assert_that!(hunks[0].right_range()).is_equal_to(0..6);
+}
+
+#[test]
+fn hunks_without_context() {
+    let left = [1, 2, 3, 4];
+    let right = [2, 3, 4, 5];
+    let diffs = diff(&left, &right);
+
+    let hunks = group_hunks(&diffs, 0);
+
+    assert_that!(hunks.len()).is_equal_to(2);
+    assert_that!(hunks[0].left_range()).is_equal_to(0..1);
+    assert_that!(hunks[0].right_range()).is_equal_to(0..0);
+    assert_that!(hunks[0].diffs()).is_equal_to(
+        &[Diff::Left {
+            index: 0,
+            length: 1,
+        }][..],
+    );
+    assert_that!(hunks[1].left_range()).is_equal_to(4..4);
+    assert_that!(hunks[1].right_range()).is_equal_to(3..4);
+    assert_that!(hunks[1].diffs()).is_equal_to(
+        &[Diff::Right {
+            index: 3,
+            length: 1,
+        }][..],
+    );
+}
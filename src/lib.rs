@@ -1,9 +1,12 @@
 //! Find the differences of two sequences.
 //!
 //! A diff function that finds the longest common subsequence (LCS). The output
-//! can easily be transformed to a shortest edit script (SES).
+//! can easily be transformed to a shortest edit script (SES), see
+//! [`EditScript`].
 //!
 //! The implementation is base on the [difference algorithm by Eugene W. Myers].
+//! It uses a linear space variant of the algorithm, so the memory
+//! needed is proportional to the sum of the lengths of the two sequences.
 //!
 //! [difference algorithm by Eugene W. Myers]: http://www.xmailserver.org/diff2.pdf
 
@@ -14,6 +17,9 @@ mod std {
     extern crate alloc;
     pub use alloc::*;
     pub use core::*;
+
+    // modules present in both `alloc` and `core`
+    pub use alloc::{fmt, slice, str};
 }
 
 #[cfg(feature = "std")]
@@ -21,450 +27,520 @@ mod std {
     pub use std::*;
 }
 
-use crate::std::{
-    boxed::Box,
-    ops::{Index, IndexMut},
-    vec,
-    vec::Vec,
+mod cleanup;
+mod edit_script;
+mod error;
+#[cfg(feature = "graphemes")]
+mod graphemes;
+mod histogram;
+mod hook;
+mod hunk;
+mod limits;
+mod lines;
+mod merge;
+mod myers;
+mod onp;
+mod options;
+mod patch;
+mod patience;
+mod str_diff;
+mod tokens;
+mod unified;
+
+pub use cleanup::{cleanup_efficiency, cleanup_semantic, indent_heuristic, DEFAULT_EDIT_COST};
+pub use edit_script::{EditOp, EditScript};
+pub use error::{DiffError, ParseError, PatchError, Side};
+#[cfg(feature = "graphemes")]
+pub use graphemes::{diff_graphemes, try_diff_graphemes, GraphemeTokenizer};
+pub use hook::{Capture, DiffHook, Replace};
+pub use hunk::{group_hunks, Hunk};
+pub use limits::{Deadline, Limits};
+pub use lines::{diff_lines, line_range, try_diff_lines};
+pub use merge::{
+    merge3, merge3_lines, try_merge3, try_merge3_lines, ConflictMarkers, Merge, MergeRegion,
+};
+pub use options::{Cleanup, DiffOptions, Granularity};
+pub use patch::{
+    apply, Change, ChangeOutcome, FuzzyApplied, FuzzyPatch, Patch, DEFAULT_FUZZ, DEFAULT_MAX_OFFSET,
 };
+pub use str_diff::{diff_str_ranges, try_diff_str_ranges, StrDiff};
+pub use tokens::{
+    diff_tokens, diff_words, token_range, try_diff_tokens, try_diff_words, PunctuationTokenizer,
+    Tokenizer, WhitespaceTokenizer, WordBoundaryTokenizer,
+};
+pub use unified::{parse_unified, FilePatch, UnifiedDiff, DEFAULT_CONTEXT_LINES};
+
+use crate::std::vec::Vec;
 
 // workaround for false positive 'unused extern crate' warnings until
 // Rust issue [#95513](https://github.com/rust-lang/rust/issues/95513) is fixed
 #[cfg(test)]
 mod dummy_extern_uses {
     use proptest as _;
+    use serde_json as _;
 }
 
 /// Max length of the sequences that is supported.
+///
+/// The search stores the furthest reaching point of each diagonal of the
+/// edit graph. There are `2 * (left.len() + right.len()) + 3` diagonals,
+/// which are indexed by `isize`, so the length of each sequence is limited
+/// to a quarter of `isize::MAX`.
 #[must_use]
-pub fn max_sequence_length() -> usize {
-    Trace::max_sequence_length()
+#[allow(clippy::cast_sign_loss)]
+pub const fn max_sequence_length() -> usize {
+    (isize::MAX as usize - 3) / 4
 }
 
 /// Find the common subsequences and differences between two strings.
 ///
 /// Each of the two strings must not be longer than the max supported length
 /// [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the strings is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_str()`] for a variant that returns an error instead.
 #[must_use]
 pub fn diff_str(left: &str, right: &str) -> Vec<Diff> {
-    diff(
-        &left.chars().collect::<Vec<_>>(),
-        &right.chars().collect::<Vec<_>>(),
-    )
+    DiffOptions::new().diff_str(left, right)
 }
 
 /// Find the common subsequences and differences between two slices.
 ///
 /// Each of the two slices must not be longer than the max supported length
 /// [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff()`] for a variant that returns an error instead.
 #[must_use]
 pub fn diff<T>(left: &[T], right: &[T]) -> Vec<Diff>
 where
     T: PartialEq,
 {
-    let trace = find_shortest_trace(left, right);
-    list_diffs(left, right, &trace)
+    try_diff(left, right).unwrap_or_else(|error| panic!("{error}"))
 }
 
-/// A subsequence that is present in either of two sequences or in both.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Diff {
-    /// A subsequence that is only present in the left sequence. It starts at
-    /// the specified index [`Diff::Left::index`] into the left sequence and
-    /// has a length of [`Diff::Left::length`].
-    ///
-    /// This is equivalent to a 'remove' in an edit script.
-    Left {
-        /// The index into the left sequence where the subsequence starts.
-        index: usize,
-        /// The length of the subsequence.
-        length: usize,
-    },
-
-    /// A common subsequence of both sequences. This subsequence is present in
-    /// both, the left and the right sequence.
-    Both {
-        /// The index into the left sequence where the common subsequence
-        /// starts.
-        left_index: usize,
-        /// The index into the right sequence where the common subsequence
-        /// starts.
-        right_index: usize,
-        /// The length of the common subsequence.
-        length: usize,
-    },
+/// Find the common subsequences and differences between two strings.
+///
+/// This is the fallible variant of [`diff_str()`]. It returns an error if one
+/// of the strings is longer than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated.
+pub fn try_diff_str(left: &str, right: &str) -> Result<Vec<Diff>, DiffError> {
+    DiffOptions::new().try_diff_str(left, right)
+}
 
-    /// A subsequence that is only present in the right sequence. It starts at
-    /// the specified index [`Diff::Left::index`] into the right sequence and
-    /// has a length of [`Diff::Left::length`].
-    ///
-    /// This is equivalent to an 'insert' in an edit script.
-    Right {
-        /// The index into the right sequence where the subsequence starts.
-        index: usize,
-        /// The length of the subsequence.
-        length: usize,
-    },
+/// Find the common subsequences and differences between two slices.
+///
+/// This is the fallible variant of [`diff()`]. It returns an error if one of
+/// the slices is longer than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated.
+pub fn try_diff<T>(left: &[T], right: &[T]) -> Result<Vec<Diff>, DiffError>
+where
+    T: PartialEq,
+{
+    DiffOptions::new().try_diff_slices(left, right)
 }
 
-/// The shortest trace found in the edit space.
+/// Find the common subsequences and differences between two slices using
+/// the given function to compare elements.
 ///
-/// The index *k* is calculated as *k = x - y*. *d* is the depth in the graph
-/// that is examined. The values stored in the matrix are the best *x* value
-/// that can be achieved at each point.
+/// Two elements are considered equal if the function `eq` returns `true`.
+/// This way elements can be compared by some of their fields only or floats
+/// can be compared within a tolerance. The elements do not need to implement
+/// `PartialEq`.
 ///
-/// # Layout
+/// Each of the two slices must not be longer than the max supported length
+/// [`max_sequence_length()`].
 ///
-/// ```text
-///     |                k
-///     |-5 -4 -3 -2 -1  0  1  2  3  4  5
-/// ----+---------------------------------
-///   0 |                o
-///   1 |             o  o  o
-/// d 2 |          o  o  o  o  o
-///   3 |       o  o  o  o  o  o  o
-///   4 |    o  o  o  o  o  o  o  o  o
-///   5 | o  o  o  o  o  o  o  o  o  o  o
-/// ```
+/// # Panics
+///
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_by()`] for a variant that returns an error instead.
 ///
 /// # Example
 ///
-/// Trace for diff of sequences 'ABCABBA' and 'CBABAC':
-///
-/// ```text
-///     |                k
-///     |-5 -4 -3 -2 -1  0  1  2  3  4  5
-/// ----+---------------------------------
-///   0 |                0
-///   1 |             0  0  1
-/// d 2 |          2  0  2  1  3
-///   3 |       3  2  4  2  5  3  5
-///   4 |       3  4  4  5  5  7  5  7
-///   5 |       3  4  5  5  7  7  5  7
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ShortestTrace {
-    data: Box<[isize]>,
-    len: isize,
+/// use sdiff::{diff_by, Diff};
+///
+/// let left = [1.0, 2.0, 3.0];
+/// let right = [1.0001, 2.5, 2.9999];
+///
+/// let diffs = diff_by(&left, &right, |a: &f64, b: &f64| (a - b).abs() < 0.001);
+///
+/// assert_eq!(diffs[0], Diff::Both { left_index: 0, right_index: 0, length: 1 });
+/// ```
+#[must_use]
+pub fn diff_by<T, F>(left: &[T], right: &[T], eq: F) -> Vec<Diff>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    try_diff_by(left, right, eq).unwrap_or_else(|error| panic!("{error}"))
 }
 
-impl ShortestTrace {
-    /// The length of the found shortest trace.
-    #[must_use]
-    #[allow(clippy::cast_sign_loss, clippy::len_without_is_empty)]
-    pub const fn len(&self) -> usize {
-        self.len as usize
-    }
-
-    /// A slice of the 2D-matrix containing the recorded trace.
-    #[must_use]
-    pub const fn data(&self) -> &[isize] {
-        &self.data
-    }
-
-    /// Get a shared reference to an element in the recorded trace.
-    #[must_use]
-    pub fn get(&self, d: isize, k: isize) -> &isize {
-        let idx = Trace::calculate_index(d, k);
-        &self.data[idx]
-    }
-
-    /// Get a mutable reference to an element in the recorded trace.
-    #[must_use]
-    pub fn get_mut(&mut self, d: isize, k: isize) -> &mut isize {
-        let idx = Trace::calculate_index(d, k);
-        &mut self.data[idx]
-    }
+/// Find the common subsequences and differences between two slices using
+/// the given function to compare elements.
+///
+/// This is the fallible variant of [`diff_by()`].
+pub fn try_diff_by<T, F>(left: &[T], right: &[T], eq: F) -> Result<Vec<Diff>, DiffError>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    DiffOptions::new().try_diff_slices_by(left, right, eq)
 }
 
-impl Index<(isize, isize)> for ShortestTrace {
-    type Output = isize;
-
-    fn index(&self, (d, k): (isize, isize)) -> &Self::Output {
-        self.get(d, k)
-    }
+/// Find the common subsequences and differences between two slices and pass
+/// them on to the given hook while they are found.
+///
+/// The hook receives the same subsequences as listed by [`diff()`], but
+/// without collecting them first, see [`DiffHook`]. For two empty slices only
+/// [`DiffHook::finish()`] is called.
+///
+/// Each of the two slices must not be longer than the max supported length
+/// [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_with_hook()`] for a variant that returns an error instead.
+pub fn diff_with_hook<T, H>(left: &[T], right: &[T], hook: &mut H)
+where
+    T: PartialEq,
+    H: DiffHook + ?Sized,
+{
+    try_diff_with_hook(left, right, hook).unwrap_or_else(|error| panic!("{error}"));
 }
 
-impl IndexMut<(isize, isize)> for ShortestTrace {
-    fn index_mut(&mut self, (d, k): (isize, isize)) -> &mut Self::Output {
-        self.get_mut(d, k)
-    }
+/// Find the common subsequences and differences between two slices and pass
+/// them on to the given hook while they are found.
+///
+/// This is the fallible variant of [`diff_with_hook()`]. If an error is
+/// returned, the hook has not been called.
+pub fn try_diff_with_hook<T, H>(left: &[T], right: &[T], hook: &mut H) -> Result<(), DiffError>
+where
+    T: PartialEq,
+    H: DiffHook + ?Sized,
+{
+    check_sequence_length(Side::Left, left.len())?;
+    check_sequence_length(Side::Right, right.len())?;
+
+    myers::diff_with_hook(left, right, PartialEq::eq, &mut Limits::new(), hook)
 }
 
-/// Recorded path through the edit space.
+/// Find the common subsequences and differences between two slices using
+/// the given algorithm.
+///
+/// Each of the two slices must not be longer than the max supported length
+/// [`max_sequence_length()`].
+///
+/// # Panics
 ///
-/// The index *k* is calculated as *k = x - y*. *d* is the depth in the graph
-/// that is examined. The values stored in the matrix are the best *x* value
-/// that can be achieved at each point.
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_with_algorithm()`] for a variant that returns an error instead.
 ///
-/// # Layout
+/// # Example
 ///
-/// ```text
-///     |                k
-///     |-5 -4 -3 -2 -1  0  1  2  3  4  5
-/// ----+---------------------------------
-///   0 |                o
-///   1 |             o  o  o
-/// d 2 |          o  o  o  o  o
-///   3 |       o  o  o  o  o  o  o
-///   4 |    o  o  o  o  o  o  o  o  o
-///   5 | o  o  o  o  o  o  o  o  o  o  o
 /// ```
+/// use sdiff::{diff_with_algorithm, Algorithm, Diff};
 ///
-/// # Example
+/// let left = ["a", "b", "c", "d"];
+/// let right = ["c", "d", "a", "b"];
+///
+/// let diffs = diff_with_algorithm(&left, &right, Algorithm::Patience);
 ///
-/// Trace for diff of sequences 'ABCABBA' and 'CBABAC':
-///
-/// ```text
-///     |                k
-///     |-5 -4 -3 -2 -1  0  1  2  3  4  5
-/// ----+---------------------------------
-///   0 |                0
-///   1 |             0  0  1
-/// d 2 |          2  0  2  1  3
-///   3 |       3  2  4  2  5  3  5
-///   4 |       3  4  4  5  5  7  5  7
-///   5 |       3  4  5  5  7  7  5  7
+/// assert_eq!(
+///     diffs,
+///     [
+///         Diff::Left { index: 0, length: 2 },
+///         Diff::Both { left_index: 2, right_index: 0, length: 2 },
+///         Diff::Right { index: 2, length: 2 },
+///     ]
+/// );
 /// ```
-struct Trace {
-    data: Box<[isize]>,
+#[must_use]
+pub fn diff_with_algorithm<T>(left: &[T], right: &[T], algorithm: Algorithm) -> Vec<Diff>
+where
+    T: Ord,
+{
+    try_diff_with_algorithm(left, right, algorithm).unwrap_or_else(|error| panic!("{error}"))
 }
 
-impl Trace {
-    /// Max length of the sequences that is supported.
-    #[must_use]
-    #[allow(
-        clippy::cast_precision_loss,
-        clippy::cast_possible_truncation,
-        clippy::cast_sign_loss
-    )]
-    pub fn max_sequence_length() -> usize {
-        2 * (libm::sqrt(isize::MAX as f64) as usize - 2)
-    }
+/// Find the common subsequences and differences between two slices using
+/// the given algorithm.
+///
+/// This is the fallible variant of [`diff_with_algorithm()`].
+pub fn try_diff_with_algorithm<T>(
+    left: &[T],
+    right: &[T],
+    algorithm: Algorithm,
+) -> Result<Vec<Diff>, DiffError>
+where
+    T: Ord,
+{
+    DiffOptions::new()
+        .algorithm(algorithm)
+        .try_diff_ord_slices(left, right)
+}
 
-    /// Constructs a new `Trace` with pre-allocated slots.
-    ///
-    /// * *d* is iterated from *0* to max depth
-    /// * For each value of *d* we need *1 + d* slots
-    /// * sum of integers is *n * (n + 1) / 2*
-    /// * *k* is iterated from *-d* to *+d* on every other.
-    pub fn new(left_len: usize, right_len: usize) -> Self {
-        let max_sequence_length = Self::max_sequence_length();
-        assert!(
-            left_len <= max_sequence_length,
-            "the left sequence is longer than the max supported length of {max_sequence_length}",
-        );
-        assert!(
-            right_len <= max_sequence_length,
-            "the right sequence is longer than the max supported length of {max_sequence_length}",
-        );
-
-        let max_depth = left_len + right_len;
-        let num_slots = (max_depth + 1) * (max_depth + 2) / 2;
-
-        Self {
-            data: vec![0; num_slots].into(),
-        }
-    }
+/// Find the common subsequences and differences between two slices as long
+/// as the given limits are not exceeded.
+///
+/// If the limits are exceeded, the search stops exploring and the diffs are
+/// approximated, see [`Limits`]. The returned diffs are always valid, but
+/// may not be minimal.
+///
+/// Each of the two slices must not be longer than the max supported length
+/// [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_with_limits()`] for a variant that returns an error instead.
+#[must_use]
+pub fn diff_with_limits<T>(left: &[T], right: &[T], limits: Limits<'_>) -> Vec<Diff>
+where
+    T: PartialEq,
+{
+    try_diff_with_limits(left, right, limits).unwrap_or_else(|error| panic!("{error}"))
+}
 
-    /// Calculates the index into the internal matrix for *(d, k)*.
-    #[inline]
-    #[allow(clippy::cast_sign_loss)]
-    fn calculate_index(d: isize, k: isize) -> usize {
-        debug_assert!(k >= -d && k <= d, "invalid index in matrix {:?}", (d, k));
-        let k_offset = d * (d + 1) / 2;
-        // *k* goes from *-d* to *d* so we need to map [-d, d] -> [0, 2d]
-        let unsigned_k = k + d;
-        (unsigned_k / 2 + k_offset) as usize
-    }
+/// Find the common subsequences and differences between two slices as long
+/// as the given limits are not exceeded.
+///
+/// This is the fallible variant of [`diff_with_limits()`].
+pub fn try_diff_with_limits<T>(
+    left: &[T],
+    right: &[T],
+    mut limits: Limits<'_>,
+) -> Result<Vec<Diff>, DiffError>
+where
+    T: PartialEq,
+{
+    check_sequence_length(Side::Left, left.len())?;
+    check_sequence_length(Side::Right, right.len())?;
 
-    #[must_use]
-    pub fn get(&self, d: isize, k: isize) -> &isize {
-        let idx = Self::calculate_index(d, k);
-        &self.data[idx]
-    }
+    myers::diff_within_limits(left, right, PartialEq::eq, &mut limits)
+}
 
-    #[must_use]
-    pub fn get_mut(&mut self, d: isize, k: isize) -> &mut isize {
-        let idx = Self::calculate_index(d, k);
-        &mut self.data[idx]
+/// Dispatches to the given algorithm. The lengths of the sequences must have
+/// been checked already.
+fn diff_within_limits<T>(
+    left: &[T],
+    right: &[T],
+    algorithm: Algorithm,
+    limits: &mut Limits<'_>,
+) -> Result<Vec<Diff>, DiffError>
+where
+    T: Ord,
+{
+    match algorithm {
+        Algorithm::Patience => patience::diff(left, right, limits),
+        Algorithm::Histogram => histogram::diff(left, right, limits),
+        _ => diff_by_within_limits(left, right, PartialEq::eq, algorithm, limits),
     }
 }
 
-impl Index<(isize, isize)> for Trace {
-    type Output = isize;
-
-    fn index(&self, (d, k): (isize, isize)) -> &Self::Output {
-        self.get(d, k)
+/// Dispatches to the given algorithm, which must not need to order the
+/// elements. The lengths of the sequences must have been checked already.
+fn diff_by_within_limits<T, F>(
+    left: &[T],
+    right: &[T],
+    eq: F,
+    algorithm: Algorithm,
+    limits: &mut Limits<'_>,
+) -> Result<Vec<Diff>, DiffError>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    match algorithm.resolve(left.len(), right.len()) {
+        Algorithm::Myers | Algorithm::Auto => myers::diff_within_limits(left, right, eq, limits),
+        Algorithm::Onp => onp::diff(left, right, eq, limits),
+        Algorithm::Patience | Algorithm::Histogram => Err(DiffError::OrderRequired { algorithm }),
     }
 }
 
-impl IndexMut<(isize, isize)> for Trace {
-    fn index_mut(&mut self, (d, k): (isize, isize)) -> &mut Self::Output {
-        self.get_mut(d, k)
-    }
+/// Find the common subsequences and differences between two slices
+/// comparing the keys extracted from the elements.
+///
+/// The function `key` is called exactly once for each element of the two
+/// slices. Two elements are considered equal if their keys are equal. The
+/// elements do not need to implement `PartialEq`.
+///
+/// Each of the two slices must not be longer than the max supported length
+/// [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_by_key()`] for a variant that returns an error instead.
+#[must_use]
+pub fn diff_by_key<T, K, F>(left: &[T], right: &[T], key: F) -> Vec<Diff>
+where
+    K: PartialEq,
+    F: FnMut(&T) -> K,
+{
+    try_diff_by_key(left, right, key).unwrap_or_else(|error| panic!("{error}"))
 }
 
-/// Find the shortest path from *(0,0)* till the end of the edit graph.
-#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
-fn find_shortest_trace<T>(left: &[T], right: &[T]) -> ShortestTrace
+/// Find the common subsequences and differences between two slices
+/// comparing the keys extracted from the elements.
+///
+/// This is the fallible variant of [`diff_by_key()`].
+pub fn try_diff_by_key<T, K, F>(left: &[T], right: &[T], mut key: F) -> Result<Vec<Diff>, DiffError>
 where
-    T: PartialEq,
+    K: PartialEq,
+    F: FnMut(&T) -> K,
 {
-    let left_len = left.len();
-    let right_len = right.len();
-
-    let max_depth = left_len + right_len;
-
-    let mut trace = Trace::new(left_len, right_len);
-
-    let max_depth = max_depth as isize;
-    let left_len = left_len as isize;
-    let right_len = right_len as isize;
-
-    for d in 0..=max_depth {
-        for k in (-d..=d).step_by(2) {
-            let mut x = if d == 0 {
-                0
-            } else if k == -d {
-                trace[(d - 1, k + 1)]
-            } else if k == d {
-                trace[(d - 1, k - 1)] + 1
-            } else {
-                let left = trace[(d - 1, k - 1)];
-                let right = trace[(d - 1, k + 1)];
-                if left < right {
-                    right
-                } else {
-                    left + 1
-                }
-            };
-
-            let mut y = x - k;
-            debug_assert!(
-                y >= 0,
-                "y should always be greater than or equal to 0, but is: {y:?}"
-            );
-
-            #[allow(clippy::suspicious_operation_groupings)]
-            while x < left_len && y < right_len && left[x as usize] == right[y as usize] {
-                x += 1;
-                y += 1;
-            }
-
-            trace[(d, k)] = x;
-
-            if x >= left_len && y >= right_len {
-                return ShortestTrace {
-                    data: trace.data,
-                    len: d,
-                };
-            }
-        }
-    }
+    check_sequence_length(Side::Left, left.len())?;
+    check_sequence_length(Side::Right, right.len())?;
 
-    panic!("length of a trace is longer than the maximum, which is `left.len() + right.len()`")
+    let left_keys = left.iter().map(&mut key).collect::<Vec<_>>();
+    let right_keys = right.iter().map(&mut key).collect::<Vec<_>>();
+    try_diff(&left_keys, &right_keys)
 }
 
-/// List common subsequences and differences between two sequences by
-/// backtracking the given trace.
-#[allow(clippy::cast_possible_wrap)]
-fn list_diffs<T>(left: &[T], right: &[T], trace: &ShortestTrace) -> Vec<Diff> {
-    if left.len() + right.len() == 0 {
-        return vec![Diff::Both {
-            left_index: 0,
-            right_index: 0,
-            length: 0,
-        }];
+const fn check_sequence_length(side: Side, length: usize) -> Result<(), DiffError> {
+    let max_length = max_sequence_length();
+    if length > max_length {
+        return Err(DiffError::SequenceTooLong {
+            side,
+            length,
+            max_length,
+        });
     }
+    Ok(())
+}
 
-    let mut x = left.len() as isize;
-    let mut y = right.len() as isize;
-
-    let mut diffs = Vec::new();
-
-    for d in (0..=trace.len).rev() {
-        let k = x - y;
-
-        let prev_k = if d == 0 {
-            0
-        } else if k == -d {
-            k + 1
-        } else if k == d {
-            k - 1
-        } else {
-            let left = trace[(d - 1, k - 1)];
-            let right = trace[(d - 1, k + 1)];
-            if left < right {
-                k + 1
-            } else {
-                k - 1
-            }
-        };
-
-        let prev_x = if d == 0 { 0 } else { trace[(d - 1, prev_k)] };
-        let prev_y = prev_x - prev_k;
-
-        while x > prev_x && y > prev_y {
-            x -= 1;
-            y -= 1;
-            if y < 0 {
-                y = 0;
-            }
-            if let Some(Diff::Both {
-                left_index,
-                right_index,
-                length,
-            }) = diffs.last_mut()
-            {
-                *left_index -= 1;
-                *right_index -= 1;
-                *length += 1;
-            } else {
-                #[allow(clippy::cast_sign_loss)]
-                diffs.push(Diff::Both {
-                    left_index: x as usize,
-                    right_index: y as usize,
-                    length: 1,
-                });
-            }
-        }
+/// The algorithm used to find the differences of two sequences.
+///
+/// All algorithms return the diffs in the same form, but they may find
+/// different common subsequences.
+///
+/// [`Algorithm::Patience`] and [`Algorithm::Histogram`] look up the elements
+/// by their order, so they can only be used for elements that are `Ord`, like
+/// by [`DiffOptions::diff_ord_slices()`]. Choosing them for elements that are
+/// only compared for equality is an error.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// The Myers algorithm, which finds a longest common subsequence and thus
+    /// a minimal diff.
+    #[default]
+    Myers,
+
+    /// The patience diff algorithm, which anchors the diff on the elements
+    /// that occur exactly once in each of the two sequences and uses the
+    /// Myers algorithm for the gaps in between.
+    ///
+    /// The diff is not necessarily minimal, but is often easier to read for
+    /// source code, as it matches unique lines like function signatures
+    /// instead of frequent ones like blank lines or closing braces.
+    Patience,
+
+    /// The histogram diff algorithm as implemented by git, which extends the
+    /// patience diff by anchoring on the elements that occur least often
+    /// instead of unique elements only.
+    ///
+    /// The common subsequences are the ones git finds, but git always slides
+    /// the changes afterward, so a block of changes that can be shifted may
+    /// be placed differently than by `git diff --histogram`. Use
+    /// [`indent_heuristic()`] to slide the changes of two texts like git does
+    /// with its default indent heuristic.
+    Histogram,
+
+    /// The *O(NP)* algorithm by Wu, Manber, Myers and Miller, which finds a
+    /// minimal diff like the Myers algorithm.
+    ///
+    /// Its running time depends on the number of deletions from the shorter
+    /// sequence instead of the number of all differences. It is much faster
+    /// than the Myers algorithm if one sequence is much longer than the
+    /// other. Like the Myers algorithm, it needs memory linear in the lengths
+    /// of the sequences.
+    Onp,
+
+    /// Chooses [`Algorithm::Onp`] if one sequence is at least
+    /// [`SKEWED_LENGTH_RATIO`] times as long as the other one and
+    /// [`Algorithm::Myers`] otherwise.
+    Auto,
+}
 
-        if d > 0 {
-            if prev_y == y {
-                if let Some(Diff::Left { index, length }) = diffs.last_mut() {
-                    *index -= 1;
-                    *length += 1;
+/// The ratio of the lengths of two sequences from which on
+/// [`Algorithm::Auto`] chooses the *O(NP)* algorithm.
+pub const SKEWED_LENGTH_RATIO: usize = 4;
+
+impl Algorithm {
+    /// Returns the algorithm to use for sequences of the given lengths.
+    const fn resolve(self, left_len: usize, right_len: usize) -> Self {
+        match self {
+            Self::Auto => {
+                let (shorter, longer) = if left_len <= right_len {
+                    (left_len, right_len)
                 } else {
-                    #[allow(clippy::cast_sign_loss)]
-                    diffs.push(Diff::Left {
-                        index: prev_x as usize,
-                        length: 1,
-                    });
-                }
-            } else if prev_x == x {
-                if let Some(Diff::Right { index, length }) = diffs.last_mut() {
-                    *index -= 1;
-                    *length += 1;
+                    (right_len, left_len)
+                };
+                if longer >= shorter.saturating_mul(SKEWED_LENGTH_RATIO) {
+                    Self::Onp
                 } else {
-                    #[allow(clippy::cast_sign_loss)]
-                    diffs.push(Diff::Right {
-                        index: prev_y as usize,
-                        length: 1,
-                    });
+                    Self::Myers
                 }
-            } else {
-                unreachable!("we should not come here!")
-            }
+            },
+            _ => self,
         }
-
-        x = prev_x;
-        y = prev_y;
     }
+}
+
+/// A subsequence that is present in either of two sequences or in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Diff {
+    /// A subsequence that is only present in the left sequence. It starts at
+    /// the specified index [`Diff::Left::index`] into the left sequence and
+    /// has a length of [`Diff::Left::length`].
+    ///
+    /// This is equivalent to a 'remove' in an edit script.
+    Left {
+        /// The index into the left sequence where the subsequence starts.
+        index: usize,
+        /// The length of the subsequence.
+        length: usize,
+    },
 
-    diffs.reverse();
-    diffs
+    /// A common subsequence of both sequences. This subsequence is present in
+    /// both, the left and the right sequence.
+    Both {
+        /// The index into the left sequence where the common subsequence
+        /// starts.
+        left_index: usize,
+        /// The index into the right sequence where the common subsequence
+        /// starts.
+        right_index: usize,
+        /// The length of the common subsequence.
+        length: usize,
+    },
+
+    /// A subsequence that is only present in the right sequence. It starts at
+    /// the specified index [`Diff::Left::index`] into the right sequence and
+    /// has a length of [`Diff::Left::length`].
+    ///
+    /// This is equivalent to an 'insert' in an edit script.
+    Right {
+        /// The index into the right sequence where the subsequence starts.
+        index: usize,
+        /// The length of the subsequence.
+        length: usize,
+    },
 }
 
+#[cfg(all(test, feature = "std"))]
+mod assertions;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,118 @@
+//! Limits for the search of the differences of two sequences.
+
+use crate::std::boxed::Box;
+use crate::std::fmt::{self, Debug};
+
+/// A deadline for the search of the differences of two sequences.
+///
+/// It is implemented for closures returning `true` when the deadline has
+/// been exceeded and, with the feature `std`, for `std::time::Instant`.
+pub trait Deadline {
+    /// Returns whether the deadline has been exceeded.
+    fn is_exceeded(&mut self) -> bool;
+}
+
+impl<F> Deadline for F
+where
+    F: FnMut() -> bool,
+{
+    fn is_exceeded(&mut self) -> bool {
+        self()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Deadline for std::time::Instant {
+    fn is_exceeded(&mut self) -> bool {
+        Self::now() >= *self
+    }
+}
+
+/// Limits for the search of the differences of two sequences.
+///
+/// The time and memory needed to find a minimal diff grow with the product
+/// of the length of the sequences and the number of differences. For large
+/// sequences with many differences this can take very long. The search can
+/// be limited by the max number of differences, the cost, and by a deadline.
+///
+/// If the max cost is exceeded, the search stops exploring and continues from
+/// the point it got furthest, like the heuristics of GNU diff and git do.
+/// After the deadline has been exceeded, all elements that have not been
+/// diffed yet are listed as removed and inserted. In both cases the result is
+/// a valid diff, which is not necessarily minimal.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_with_limits, Diff, Limits};
+///
+/// let left = ['a', 'b', 'c', 'd', 'e'];
+/// let right = ['a', 'c', 'b', 'd', 'e'];
+///
+/// let diffs = diff_with_limits(&left, &right, Limits::new().max_cost(0));
+///
+/// assert_eq!(
+///     diffs,
+///     [
+///         Diff::Both { left_index: 0, right_index: 0, length: 1 },
+///         Diff::Left { index: 1, length: 2 },
+///         Diff::Right { index: 1, length: 2 },
+///         Diff::Both { left_index: 3, right_index: 3, length: 2 },
+///     ]
+/// );
+/// ```
+#[derive(Default)]
+pub struct Limits<'a> {
+    max_cost: Option<usize>,
+    deadline: Option<Box<dyn Deadline + 'a>>,
+}
+
+impl<'a> Limits<'a> {
+    /// Creates limits that do not limit the search.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_cost: None,
+            deadline: None,
+        }
+    }
+
+    /// Sets the max number of differences the search explores, before it
+    /// continues with an approximation.
+    #[must_use]
+    pub const fn max_cost(mut self, max_cost: usize) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Sets the deadline after which the search stops exploring.
+    ///
+    /// The deadline is checked once per number of differences explored.
+    #[must_use]
+    pub fn deadline(mut self, deadline: impl Deadline + 'a) -> Self {
+        self.deadline = Some(Box::new(deadline));
+        self
+    }
+
+    /// Returns whether paths with the given number of differences exceed the
+    /// max cost.
+    pub(crate) fn is_too_expensive(&self, cost: usize) -> bool {
+        self.max_cost.is_some_and(|max_cost| cost > max_cost)
+    }
+
+    /// Returns whether the deadline has been exceeded.
+    pub(crate) fn is_past_deadline(&mut self) -> bool {
+        self.deadline
+            .as_mut()
+            .is_some_and(|deadline| deadline.is_exceeded())
+    }
+}
+
+impl Debug for Limits<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Limits")
+            .field("max_cost", &self.max_cost)
+            .field("deadline", &self.deadline.as_ref().map(|_| "..."))
+            .finish()
+    }
+}
@@ -0,0 +1,103 @@
+//! Find the differences between two texts line by line.
+
+use crate::std::{ops::Range, vec::Vec};
+use crate::{Diff, DiffError, DiffOptions, Granularity};
+
+/// Find the common lines and differences between two texts.
+///
+/// The texts are split into lines at each line feed (`\n`). Each line keeps
+/// its line terminator, so a line ending with `\r\n` is different from the
+/// same line ending with `\n` only, and a last line without a line terminator
+/// is different from the same line with a terminator.
+///
+/// The indices and lengths of the returned [`Diff`]s count lines. Use
+/// [`line_range()`] to get the byte range of the lines in the original text.
+///
+/// Each of the two texts must not have more lines than the max supported
+/// length [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the texts has more lines than the max supported length
+/// or if the memory needed to find the differences can not be allocated. See
+/// [`try_diff_lines()`] for a variant that returns an error instead.
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+#[must_use]
+pub fn diff_lines(left: &str, right: &str) -> Vec<Diff> {
+    DiffOptions::new()
+        .granularity(Granularity::Lines)
+        .diff_str(left, right)
+}
+
+/// Find the common lines and differences between two texts.
+///
+/// This is the fallible variant of [`diff_lines()`]. It returns an error if
+/// one of the texts has more lines than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated.
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+pub fn try_diff_lines(left: &str, right: &str) -> Result<Vec<Diff>, DiffError> {
+    DiffOptions::new()
+        .granularity(Granularity::Lines)
+        .try_diff_str(left, right)
+}
+
+/// Returns the byte range of `length` lines starting at the line `index`
+/// within the given `text`.
+///
+/// The lines are counted the same way as by [`diff_lines()`], so the range
+/// for a [`Diff`] returned by [`diff_lines()`] can be used to slice the
+/// original text. Returns `None` if the lines are not within the text.
+///
+/// The text is scanned from its start up to the last requested line on
+/// each call.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_lines, line_range, Diff};
+///
+/// let left = "one\ntwo\nthree\n";
+/// let right = "one\nthree\n";
+///
+/// let diffs = diff_lines(left, right);
+///
+/// let Diff::Left { index, length } = diffs[1] else {
+///     panic!("expected a diff of type `Diff::Left`");
+/// };
+/// let range = line_range(left, index, length);
+///
+/// assert_eq!(range, Some(4..8));
+/// assert_eq!(&left[4..8], "two\n");
+/// ```
+#[must_use]
+pub fn line_range(text: &str, index: usize, length: usize) -> Option<Range<usize>> {
+    let mut offsets = line_offsets(text).skip(index);
+    let start = offsets.next()?;
+    if length == 0 {
+        return Some(start..start);
+    }
+    let end = offsets.nth(length - 1)?;
+    Some(start..end)
+}
+
+/// Splits the text into lines, each including its line terminator.
+pub fn lines(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Byte offsets of the start of each line plus the offset of the end of the
+/// text.
+fn line_offsets(text: &str) -> impl Iterator<Item = usize> + '_ {
+    let starts = text.split_inclusive('\n').scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some(start)
+    });
+    starts.chain(Some(text.len()))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,163 @@
+use super::*;
+use asserting::prelude::*;
+
+#[test]
+fn diff_lines_of_two_empty_texts() {
+    let diffs = diff_lines("", "");
+
+    assert_that!(diffs).contains_exactly([Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 0,
+    }]);
+}
+
+#[test]
+fn diff_lines_of_texts_with_a_changed_line() {
+    let left = "lorem\nipsum\ndolor\nsit\n";
+    let right = "lorem\nipsum\ndolores\nsit\n";
+
+    let diffs = diff_lines(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Left {
+            index: 2,
+            length: 1,
+        },
+        Diff::Right {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 3,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn diff_lines_of_texts_with_inserted_and_removed_lines() {
+    let left = "lorem\nipsum\ndolor\nsit\namet";
+    let right = "lorem\ndolor\nconsectetur\nadipiscing\nsit\namet";
+
+    let diffs = diff_lines(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 1,
+            length: 1,
+        },
+        Diff::Right {
+            index: 2,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 4,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn a_missing_line_terminator_at_the_end_is_a_difference() {
+    let left = "lorem\nipsum\n";
+    let right = "lorem\nipsum";
+
+    let diffs = diff_lines(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 1,
+        },
+        Diff::Right {
+            index: 1,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn try_diff_lines_returns_the_same_diffs_as_diff_lines() {
+    let left = "lorem\nipsum\ndolor\n";
+    let right = "ipsum\ndolor\nsit\n";
+
+    let diffs = try_diff_lines(left, right);
+
+    assert_that!(diffs).has_value(diff_lines(left, right));
+}
+
+#[test]
+fn line_range_of_one_line() {
+    let text = "lorem\nipsum\ndolor\n";
+
+    assert_that!(line_range(text, 0, 1)).has_value(0..6);
+    assert_that!(line_range(text, 1, 1)).has_value(6..12);
+    assert_that!(line_range(text, 2, 1)).has_value(12..18);
+}
+
+#[test]
+fn line_range_of_multiple_lines() {
+    let text = "lorem\nipsum\ndolor\nsit";
+
+    assert_that!(line_range(text, 1, 3)).has_value(6..21);
+}
+
+#[test]
+fn line_range_of_zero_lines() {
+    let text = "lorem\nipsum\n";
+
+    assert_that!(line_range(text, 1, 0)).has_value(6..6);
+    assert_that!(line_range(text, 2, 0)).has_value(12..12);
+    assert_that!(line_range("", 0, 0)).has_value(0..0);
+}
+
+#[test]
+fn line_range_of_lines_not_within_the_text() {
+    let text = "lorem\nipsum\n";
+
+    assert_that!(line_range(text, 3, 0)).is_none();
+    assert_that!(line_range(text, 1, 2)).is_none();
+}
+
+#[test]
+fn line_ranges_of_diffs_slice_the_original_texts() {
+    let left = "lorem\nipsum\ndolor\nsit\n";
+    let right = "lorem\nipsum\ndolores\nsit\n";
+
+    let diffs = diff_lines(left, right);
+
+    let Diff::Left { index, length } = diffs[1] else {
+        panic!("expected a diff of type `Diff::Left`");
+    };
+    let left_range = line_range(left, index, length).unwrap_or_else(|| panic!("invalid range"));
+    let Diff::Right { index, length } = diffs[2] else {
+        panic!("expected a diff of type `Diff::Right`");
+    };
+    let right_range = line_range(right, index, length).unwrap_or_else(|| panic!("invalid range"));
+
+    assert_that!(&left[left_range]).is_equal_to("dolor\n");
+    assert_that!(&right[right_range]).is_equal_to("dolores\n");
+}
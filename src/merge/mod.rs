@@ -0,0 +1,374 @@
+//! Three-way merge of two sequences derived from a common base sequence.
+
+use crate::std::{fmt, vec::Vec};
+use crate::{try_diff, Diff, DiffError};
+
+/// Merges the changes of two sequences, `ours` and `theirs`, that were both
+/// derived from the same `base` sequence.
+///
+/// The differences of `ours` and `theirs` to the `base` are found by two runs
+/// of [`diff()`]. Changes made on one side only are taken over, and changes
+/// made identically on both sides are taken over once. Where both sides
+/// changed the same part of the base differently, the merge contains a
+/// [`MergeRegion::Conflict`].
+///
+/// Each of the three slices must not be longer than the max supported length
+/// [`max_sequence_length()`].
+///
+/// # Panics
+///
+/// Panics if one of the slices is longer than the max supported length or
+/// if the memory needed to find the differences can not be allocated. See
+/// [`try_merge3()`] for a variant that returns an error instead.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::merge3;
+///
+/// let base = [1, 2, 3, 4, 5];
+/// let ours = [0, 1, 2, 3, 4, 5];
+/// let theirs = [1, 2, 3, 4];
+///
+/// let merge = merge3(&base, &ours, &theirs);
+///
+/// assert!(!merge.has_conflicts());
+/// assert_eq!(merge.into_merged(), Some(vec![0, 1, 2, 3, 4]));
+/// ```
+///
+/// [`diff()`]: crate::diff
+/// [`max_sequence_length()`]: crate::max_sequence_length
+#[must_use]
+pub fn merge3<T>(base: &[T], ours: &[T], theirs: &[T]) -> Merge<T>
+where
+    T: PartialEq + Clone,
+{
+    try_merge3(base, ours, theirs).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Merges the changes of two sequences, `ours` and `theirs`, that were both
+/// derived from the same `base` sequence.
+///
+/// This is the fallible variant of [`merge3()`]. It returns an error if one
+/// of the slices is longer than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated.
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+pub fn try_merge3<T>(base: &[T], ours: &[T], theirs: &[T]) -> Result<Merge<T>, DiffError>
+where
+    T: PartialEq + Clone,
+{
+    let ours_matches = matches(base.len(), &try_diff(base, ours)?);
+    let theirs_matches = matches(base.len(), &try_diff(base, theirs)?);
+
+    let mut merge = Merge {
+        regions: Vec::new(),
+    };
+    let (mut base_pos, mut ours_pos, mut theirs_pos) = (0, 0, 0);
+    loop {
+        // stable chunk: elements of the base that are unchanged on both sides
+        let stable_len = (base_pos..base.len())
+            .take_while(|&index| {
+                ours_matches[index] == Some(ours_pos + index - base_pos)
+                    && theirs_matches[index] == Some(theirs_pos + index - base_pos)
+            })
+            .count();
+        merge.push_resolved(&base[base_pos..base_pos + stable_len]);
+        base_pos += stable_len;
+        ours_pos += stable_len;
+        theirs_pos += stable_len;
+
+        // unstable chunk: up to the next element of the base that is
+        // contained in both sides
+        let (base_end, ours_end, theirs_end) = (base_pos..base.len())
+            .find_map(|index| Some((index, ours_matches[index]?, theirs_matches[index]?)))
+            .unwrap_or((base.len(), ours.len(), theirs.len()));
+        if base_end == base_pos && ours_end == ours_pos && theirs_end == theirs_pos {
+            break;
+        }
+        let base_chunk = &base[base_pos..base_end];
+        let ours_chunk = &ours[ours_pos..ours_end];
+        let theirs_chunk = &theirs[theirs_pos..theirs_end];
+        if ours_chunk == base_chunk {
+            merge.push_resolved(theirs_chunk);
+        } else if theirs_chunk == base_chunk || ours_chunk == theirs_chunk {
+            merge.push_resolved(ours_chunk);
+        } else {
+            merge.regions.push(MergeRegion::Conflict {
+                base: base_chunk.to_vec(),
+                ours: ours_chunk.to_vec(),
+                theirs: theirs_chunk.to_vec(),
+            });
+        }
+        base_pos = base_end;
+        ours_pos = ours_end;
+        theirs_pos = theirs_end;
+    }
+    Ok(merge)
+}
+
+/// Merges the changes of two texts, `ours` and `theirs`, that were both
+/// derived from the same `base` text, line by line.
+///
+/// The texts are split into lines the same way as by [`diff_lines()`]. The
+/// merged text, including conflict markers for conflicting changes, can be
+/// rendered with [`Merge::conflict_markers()`].
+///
+/// # Panics
+///
+/// Panics if one of the texts has more lines than the max supported length
+/// or if the memory needed to find the differences can not be allocated. See
+/// [`try_merge3_lines()`] for a variant that returns an error instead.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::merge3_lines;
+///
+/// let base = "lorem\nipsum\ndolor\n";
+/// let ours = "Lorem\nipsum\ndolor\n";
+/// let theirs = "lorem\nipsum\ndolor sit\n";
+///
+/// let merge = merge3_lines(base, ours, theirs);
+///
+/// assert_eq!(
+///     merge.conflict_markers().to_string(),
+///     "Lorem\nipsum\ndolor sit\n"
+/// );
+/// ```
+///
+/// [`diff_lines()`]: crate::diff_lines
+#[must_use]
+pub fn merge3_lines<'a>(base: &'a str, ours: &'a str, theirs: &'a str) -> Merge<&'a str> {
+    try_merge3_lines(base, ours, theirs).unwrap_or_else(|error| panic!("{error}"))
+}
+
+/// Merges the changes of two texts, `ours` and `theirs`, that were both
+/// derived from the same `base` text, line by line.
+///
+/// This is the fallible variant of [`merge3_lines()`]. It returns an error if
+/// one of the texts has more lines than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated.
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+pub fn try_merge3_lines<'a>(
+    base: &'a str,
+    ours: &'a str,
+    theirs: &'a str,
+) -> Result<Merge<&'a str>, DiffError> {
+    try_merge3(
+        &base.split_inclusive('\n').collect::<Vec<_>>(),
+        &ours.split_inclusive('\n').collect::<Vec<_>>(),
+        &theirs.split_inclusive('\n').collect::<Vec<_>>(),
+    )
+}
+
+/// The result of a three-way merge as returned by [`merge3()`].
+///
+/// A merge is a list of regions that are either resolved automatically or
+/// conflicting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Merge<T> {
+    regions: Vec<MergeRegion<T>>,
+}
+
+impl<T> Merge<T> {
+    /// Returns the regions of this merge in order.
+    ///
+    /// Adjacent resolved regions are combined into one.
+    #[must_use]
+    pub fn regions(&self) -> &[MergeRegion<T>] {
+        &self.regions
+    }
+
+    /// Returns the regions of this merge in order.
+    #[must_use]
+    pub fn into_regions(self) -> Vec<MergeRegion<T>> {
+        self.regions
+    }
+
+    /// Returns whether this merge contains at least one conflict.
+    #[must_use]
+    pub fn has_conflicts(&self) -> bool {
+        self.regions
+            .iter()
+            .any(|region| matches!(region, MergeRegion::Conflict { .. }))
+    }
+
+    /// Returns the merged sequence if this merge does not contain any
+    /// conflicts.
+    #[must_use]
+    pub fn into_merged(self) -> Option<Vec<T>> {
+        let mut merged = Vec::new();
+        for region in self.regions {
+            match region {
+                MergeRegion::Resolved(elements) => merged.extend(elements),
+                MergeRegion::Conflict { .. } => return None,
+            }
+        }
+        Some(merged)
+    }
+
+    fn push_resolved(&mut self, elements: &[T])
+    where
+        T: Clone,
+    {
+        if elements.is_empty() {
+            return;
+        }
+        if let Some(MergeRegion::Resolved(resolved)) = self.regions.last_mut() {
+            resolved.extend_from_slice(elements);
+        } else {
+            self.regions.push(MergeRegion::Resolved(elements.to_vec()));
+        }
+    }
+}
+
+impl<'a> Merge<&'a str> {
+    /// Renders the merged text with git-style conflict markers.
+    ///
+    /// The text is rendered by the [`Display`](fmt::Display) implementation
+    /// of the returned [`ConflictMarkers`].
+    #[must_use]
+    pub const fn conflict_markers(&self) -> ConflictMarkers<'_, 'a> {
+        ConflictMarkers {
+            merge: self,
+            ours_label: "ours",
+            theirs_label: "theirs",
+        }
+    }
+}
+
+/// A region of a [`Merge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeRegion<T> {
+    /// A region that has been merged without conflicts.
+    Resolved(Vec<T>),
+    /// A region of the base that has been changed differently on both sides.
+    Conflict {
+        /// The elements of the region in the base.
+        base: Vec<T>,
+        /// The elements of the region in our sequence.
+        ours: Vec<T>,
+        /// The elements of the region in their sequence.
+        theirs: Vec<T>,
+    },
+}
+
+/// Renders a line merge with git-style conflict markers.
+///
+/// Resolved lines are rendered as they are. Each conflict is rendered as
+///
+/// ```text
+/// <<<<<<< ours
+/// our lines
+/// =======
+/// their lines
+/// >>>>>>> theirs
+/// ```
+///
+/// A conflicting line without a line terminator at the end of the text is
+/// terminated with a line feed, so the markers stay on lines of their own.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::merge3_lines;
+///
+/// let base = "lorem\nipsum\n";
+/// let ours = "lorem\nipsum dolor\n";
+/// let theirs = "lorem\nipsum sit\n";
+///
+/// let merged = merge3_lines(base, ours, theirs)
+///     .conflict_markers()
+///     .labels("HEAD", "upstream")
+///     .to_string();
+///
+/// assert_eq!(
+///     merged,
+///     "lorem\n\
+///      <<<<<<< HEAD\n\
+///      ipsum dolor\n\
+///      =======\n\
+///      ipsum sit\n\
+///      >>>>>>> upstream\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConflictMarkers<'m, 'a> {
+    merge: &'m Merge<&'a str>,
+    ours_label: &'m str,
+    theirs_label: &'m str,
+}
+
+impl<'m> ConflictMarkers<'m, '_> {
+    /// Sets the labels rendered after the `<<<<<<<` and the `>>>>>>>`
+    /// markers.
+    ///
+    /// By default, the labels are `ours` and `theirs`.
+    #[must_use]
+    pub const fn labels(mut self, ours_label: &'m str, theirs_label: &'m str) -> Self {
+        self.ours_label = ours_label;
+        self.theirs_label = theirs_label;
+        self
+    }
+}
+
+impl fmt::Display for ConflictMarkers<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for region in &self.merge.regions {
+            match region {
+                MergeRegion::Resolved(lines) => {
+                    for line in lines {
+                        f.write_str(line)?;
+                    }
+                },
+                MergeRegion::Conflict { ours, theirs, .. } => {
+                    writeln!(f, "<<<<<<< {}", self.ours_label)?;
+                    write_terminated_lines(f, ours)?;
+                    writeln!(f, "=======")?;
+                    write_terminated_lines(f, theirs)?;
+                    writeln!(f, ">>>>>>> {}", self.theirs_label)?;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the lines and terminates the last line with a line feed if it
+/// does not end with one.
+fn write_terminated_lines(f: &mut fmt::Formatter<'_>, lines: &[&str]) -> fmt::Result {
+    for line in lines {
+        f.write_str(line)?;
+        if !line.ends_with('\n') {
+            f.write_str("\n")?;
+        }
+    }
+    Ok(())
+}
+
+/// Maps each element of the base to the index of the same element in the
+/// other sequence, if the element is contained in both.
+fn matches(base_len: usize, diffs: &[Diff]) -> Vec<Option<usize>> {
+    let mut matches = Vec::with_capacity(base_len);
+    matches.resize(base_len, None);
+    for diff in diffs {
+        if let Diff::Both {
+            left_index,
+            right_index,
+            length,
+        } = *diff
+        {
+            for offset in 0..length {
+                matches[left_index + offset] = Some(right_index + offset);
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests;
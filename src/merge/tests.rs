@@ -0,0 +1,140 @@
+use super::*;
+use crate::std::{string::ToString, vec};
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn merging_changes_made_on_one_side_only_results_in_the_changed_side(
+            base in prop::collection::vec(0..6_u8, 0..=40),
+            changed in prop::collection::vec(0..6_u8, 0..=40),
+        ) {
+            prop_assert_eq!(merge3(&base, &changed, &base).into_merged(), Some(changed.clone()));
+            prop_assert_eq!(merge3(&base, &base, &changed).into_merged(), Some(changed.clone()));
+            prop_assert_eq!(merge3(&base, &changed, &changed).into_merged(), Some(changed));
+        }
+    }
+}
+
+#[test]
+fn merge_of_three_equal_sequences() {
+    let base = [1, 2, 3];
+
+    let merge = merge3(&base, &base, &base);
+
+    assert_that!(merge.regions()).is_equal_to(&[MergeRegion::Resolved(vec![1, 2, 3])][..]);
+}
+
+#[test]
+fn merge_of_three_empty_sequences() {
+    let base: [u8; 0] = [];
+
+    let merge = merge3(&base, &base, &base);
+
+    assert_that!(merge.has_conflicts()).is_false();
+    assert_that!(merge.into_regions()).is_empty();
+}
+
+#[test]
+fn changes_made_on_one_side_only_are_taken_over() {
+    let base = ['a', 'b', 'c', 'd', 'e', 'f'];
+    let ours = ['a', 'x', 'c', 'd', 'e', 'f'];
+    let theirs = ['a', 'b', 'c', 'd', 'f', 'y'];
+
+    let merge = merge3(&base, &ours, &theirs);
+
+    assert_that!(merge.into_merged()).has_value(vec!['a', 'x', 'c', 'd', 'f', 'y']);
+}
+
+#[test]
+fn identical_changes_on_both_sides_are_taken_over_once() {
+    let base = [1, 2, 3, 4];
+    let ours = [1, 5, 3, 4, 6];
+    let theirs = [1, 5, 3, 4];
+
+    let merge = merge3(&base, &ours, &theirs);
+
+    assert_that!(merge.into_merged()).has_value(vec![1, 5, 3, 4, 6]);
+}
+
+#[test]
+fn different_changes_of_the_same_region_are_a_conflict() {
+    let base = [1, 2, 3, 4];
+    let ours = [1, 5, 3, 4];
+    let theirs = [1, 6, 7, 3, 4];
+
+    let merge = merge3(&base, &ours, &theirs);
+
+    assert_that!(merge.has_conflicts()).is_true();
+    assert_that!(merge.regions()).is_equal_to(
+        &[
+            MergeRegion::Resolved(vec![1]),
+            MergeRegion::Conflict {
+                base: vec![2],
+                ours: vec![5],
+                theirs: vec![6, 7],
+            },
+            MergeRegion::Resolved(vec![3, 4]),
+        ][..],
+    );
+    assert_that!(merge.into_merged()).is_none();
+}
+
+#[test]
+fn different_insertions_at_the_same_position_are_a_conflict() {
+    let base = [1, 2];
+    let ours = [1, 3, 2];
+    let theirs = [1, 4, 2];
+
+    let merge = merge3(&base, &ours, &theirs);
+
+    assert_that!(merge.into_regions()).contains_exactly([
+        MergeRegion::Resolved(vec![1]),
+        MergeRegion::Conflict {
+            base: vec![],
+            ours: vec![3],
+            theirs: vec![4],
+        },
+        MergeRegion::Resolved(vec![2]),
+    ]);
+}
+
+#[test]
+fn render_merged_lines_without_conflicts() {
+    let base = "[server]\nport = 80\nhost = localhost\n";
+    let ours = "[server]\nport = 8080\nhost = localhost\n";
+    let theirs = "[server]\nport = 80\nhost = localhost\ntimeout = 30\n";
+
+    let merged = merge3_lines(base, ours, theirs)
+        .conflict_markers()
+        .to_string();
+
+    assert_that!(merged)
+        .is_equal_to("[server]\nport = 8080\nhost = localhost\ntimeout = 30\n".to_string());
+}
+
+#[test]
+fn render_conflicting_lines_with_markers() {
+    let base = "lorem\nipsum\ndolor";
+    let ours = "lorem\nIpsum\ndolor";
+    let theirs = "lorem\nipsum\nDolor";
+
+    let merge = merge3_lines(base, ours, theirs);
+    let merged = merge.conflict_markers().to_string();
+
+    assert_that!(merged).is_equal_to(
+        "lorem\n\
+         <<<<<<< ours\n\
+         Ipsum\n\
+         dolor\n\
+         =======\n\
+         ipsum\n\
+         Dolor\n\
+         >>>>>>> theirs\n"
+            .to_string(),
+    );
+}
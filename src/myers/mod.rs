@@ -0,0 +1,550 @@
+//! Linear-space variant of the Myers difference algorithm.
+//!
+//! Instead of recording the whole trace through the edit graph, the shortest
+//! path is divided at its middle point. The two halves on either side of the
+//! middle point are then solved recursively (divide and conquer), like in the
+//! linear space refinement described in section *4b* of the
+//! [paper by Eugene W. Myers].
+//!
+//! Different to the refinement in the paper, the middle point is not found by
+//! searching from both ends of the edit graph simultaneously, but by a
+//! forward search only. This way the found path is exactly the same one as
+//! the original algorithm finds by backtracking its trace.
+//!
+//! Before searching the edit graph, the common prefix and the common suffix
+//! of the two sequences are stripped off. Only the part in between is
+//! searched. Stripping the common suffix can lead to a different path than
+//! the one the original algorithm finds for the whole sequences. Both paths
+//! are shortest ones, so the diffs are minimal either way, but they may
+//! differ.
+//!
+//! The memory used is *O(N + M)* where *N* and *M* are the lengths of the two
+//! sequences.
+//!
+//! [paper by Eugene W. Myers]: http://www.xmailserver.org/diff2.pdf
+
+use crate::std::{
+    collections::TryReserveError,
+    ops::{Index, IndexMut},
+    vec,
+    vec::Vec,
+};
+use crate::{Capture, Diff, DiffError, DiffHook, Limits};
+
+/// Find the common subsequences and differences between two slices using
+/// the linear-space variant of the Myers algorithm as long as the given
+/// limits are not exceeded.
+///
+/// If the limits are exceeded, the diffs are approximated, see [`Limits`].
+///
+/// Returns an error if the memory needed for the search can not be
+/// allocated.
+pub fn diff_within_limits<T, F>(
+    left: &[T],
+    right: &[T],
+    eq: F,
+    limits: &mut Limits<'_>,
+) -> Result<Vec<Diff>, DiffError>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if left.len() + right.len() == 0 {
+        return Ok(vec![Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 0,
+        }]);
+    }
+
+    let mut diffs = Capture::new();
+    diff_with_hook(left, right, eq, limits, &mut diffs)?;
+    Ok(diffs.into_diffs())
+}
+
+/// Find the common subsequences and differences between two slices using
+/// the linear-space variant of the Myers algorithm and pass them on to the
+/// given hook while they are found.
+///
+/// If the limits are exceeded, the diffs are approximated, see [`Limits`].
+///
+/// Returns an error if the memory needed for the search can not be
+/// allocated. In this case, the hook has not been called.
+pub fn diff_with_hook<T, F, H>(
+    left: &[T],
+    right: &[T],
+    mut eq: F,
+    limits: &mut Limits<'_>,
+    hook: H,
+) -> Result<(), DiffError>
+where
+    F: FnMut(&T, &T) -> bool,
+    H: DiffHook,
+{
+    // Only the part between the common prefix and the common suffix is
+    // searched. This saves time and memory for sequences that differ in a few
+    // places only.
+    let prefix_len = common_prefix_len(left, right, &mut eq);
+    let suffix_len = common_suffix_len(&left[prefix_len..], &right[prefix_len..], &mut eq);
+    let left_end = left.len() - suffix_len;
+    let right_end = right.len() - suffix_len;
+
+    let max_depth = (left_end - prefix_len) + (right_end - prefix_len);
+    let mut myers = Myers {
+        left,
+        right,
+        eq,
+        furthest: Vector::try_new(max_depth)?,
+        middle_x: Vector::try_new(max_depth)?,
+        middle_y: Vector::try_new(max_depth)?,
+        diffs: DiffList::new(hook),
+    };
+    myers.diffs.push_both(0, 0, prefix_len);
+    myers.conquer_within_limits(prefix_len, left_end, prefix_len, right_end, limits);
+    myers.diffs.push_both(left_end, right_end, suffix_len);
+    myers.diffs.finish();
+    Ok(())
+}
+
+/// The result of a search that is limited by [`Limits`].
+enum Search {
+    /// The end of the edit graph has been reached with the given number of
+    /// differences.
+    Complete { depth: usize },
+    /// The max cost has been exceeded. The point *(x, y)* is the one that was
+    /// reached furthest.
+    Incomplete { x: usize, y: usize },
+    /// The deadline has been exceeded.
+    Abandoned,
+}
+
+/// State of the divide and conquer search.
+struct Myers<'a, T, F, H> {
+    left: &'a [T],
+    right: &'a [T],
+    eq: F,
+    furthest: Vector,
+    middle_x: Vector,
+    middle_y: Vector,
+    diffs: DiffList<H>,
+}
+
+impl<T, F, H> Myers<'_, T, F, H>
+where
+    F: FnMut(&T, &T) -> bool,
+    H: DiffHook,
+{
+    /// Lists the diffs of the subsequences `left[left_start..left_end]` and
+    /// `right[right_start..right_end]` as long as the limits are not
+    /// exceeded.
+    ///
+    /// If the max cost is exceeded, the diffs up to the point reached
+    /// furthest are listed and the search continues from there. If no
+    /// progress can be made or the deadline is exceeded, all remaining
+    /// elements are listed as removed and inserted.
+    fn conquer_within_limits(
+        &mut self,
+        mut left_start: usize,
+        left_end: usize,
+        mut right_start: usize,
+        right_end: usize,
+        limits: &mut Limits<'_>,
+    ) {
+        loop {
+            match self.search_within_limits(left_start, left_end, right_start, right_end, limits) {
+                Search::Complete { depth } => {
+                    self.conquer(left_start, left_end, right_start, right_end, depth);
+                    return;
+                },
+                Search::Abandoned => {
+                    self.diffs.push_left(left_start, left_end - left_start);
+                    self.diffs.push_right(right_start, right_end - right_start);
+                    return;
+                },
+                Search::Incomplete { x, y } if x == left_start && y == right_start => {
+                    self.diffs.push_left(left_start, left_end - left_start);
+                    self.diffs.push_right(right_start, right_end - right_start);
+                    return;
+                },
+                Search::Incomplete { x, y } => {
+                    let (depth, _) = self.find_middle_point(left_start, x, right_start, y, None);
+                    self.conquer(left_start, x, right_start, y, depth);
+                    left_start = x;
+                    right_start = y;
+                },
+            }
+        }
+    }
+
+    /// Lists the diffs of the subsequences `left[left_start..left_end]` and
+    /// `right[right_start..right_end]`, whose shortest path through the edit
+    /// graph has the given `depth`.
+    fn conquer(
+        &mut self,
+        mut left_start: usize,
+        left_end: usize,
+        mut right_start: usize,
+        right_end: usize,
+        depth: usize,
+    ) {
+        let prefix_len = common_prefix_len(
+            &self.left[left_start..left_end],
+            &self.right[right_start..right_end],
+            &mut self.eq,
+        );
+        self.diffs.push_both(left_start, right_start, prefix_len);
+        left_start += prefix_len;
+        right_start += prefix_len;
+
+        if left_start == left_end {
+            self.diffs.push_right(right_start, right_end - right_start);
+        } else if right_start == right_end {
+            self.diffs.push_left(left_start, left_end - left_start);
+        } else {
+            let middle = depth.div_ceil(2);
+            let (_, (x, y)) =
+                self.find_middle_point(left_start, left_end, right_start, right_end, Some(middle));
+            self.conquer(left_start, x, right_start, y, middle);
+            self.conquer(x, left_end, y, right_end, depth - middle);
+        }
+    }
+
+    /// Searches the shortest path through the edit graph of the subsequences
+    /// `left[left_start..left_end]` and `right[right_start..right_end]`.
+    ///
+    /// Returns the length of the shortest path and the point *(x, y)* where
+    /// the path leaves the diagonal it reached with `middle` differences.
+    /// The point is only determined if `middle` is specified.
+    ///
+    /// The search is the same as in the original algorithm, but instead of
+    /// recording the whole trace, we carry along for each furthest reaching
+    /// path the point where it has been at depth `middle`. This way the
+    /// found point is on the very same path that the original algorithm would
+    /// find by backtracking its trace.
+    #[allow(
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss,
+        clippy::similar_names
+    )]
+    fn find_middle_point(
+        &mut self,
+        left_start: usize,
+        left_end: usize,
+        right_start: usize,
+        right_end: usize,
+        middle: Option<usize>,
+    ) -> (usize, (usize, usize)) {
+        let left = &self.left[left_start..left_end];
+        let right = &self.right[right_start..right_end];
+        let eq = &mut self.eq;
+        let furthest = &mut self.furthest;
+        let middle_x = &mut self.middle_x;
+        let middle_y = &mut self.middle_y;
+
+        let left_len = left.len() as isize;
+        let right_len = right.len() as isize;
+        let max_depth = left_len + right_len;
+        let middle = middle.map_or(isize::MAX, |middle| middle as isize);
+
+        for d in 0..=max_depth {
+            for k in (-d..=d).step_by(2) {
+                let prev_k = if d == 0 {
+                    0
+                } else if k == -d || (k != d && furthest[k - 1] < furthest[k + 1]) {
+                    k + 1
+                } else {
+                    k - 1
+                };
+
+                let mut x = if d == 0 {
+                    0
+                } else if prev_k == k + 1 {
+                    furthest[prev_k]
+                } else {
+                    furthest[prev_k] + 1
+                };
+                let mut y = x - k;
+
+                if d == middle {
+                    middle_x[k] = x;
+                    middle_y[k] = y;
+                } else if d > middle {
+                    middle_x[k] = middle_x[prev_k];
+                    middle_y[k] = middle_y[prev_k];
+                }
+
+                #[allow(clippy::suspicious_operation_groupings)]
+                while x < left_len && y < right_len && eq(&left[x as usize], &right[y as usize]) {
+                    x += 1;
+                    y += 1;
+                }
+
+                furthest[k] = x;
+
+                if x >= left_len && y >= right_len {
+                    return (
+                        d as usize,
+                        (
+                            left_start + middle_x[k] as usize,
+                            right_start + middle_y[k] as usize,
+                        ),
+                    );
+                }
+            }
+        }
+
+        unreachable!(
+            "length of a path is longer than the maximum, which is `left.len() + right.len()`"
+        )
+    }
+
+    /// Searches the shortest path through the edit graph of the subsequences
+    /// `left[left_start..left_end]` and `right[right_start..right_end]`
+    /// until the limits are exceeded.
+    ///
+    /// Before exploring paths with one more difference, the limits are
+    /// checked. If the max cost is exceeded, the furthest point within the
+    /// edit graph, that has been reached so far, is returned.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    fn search_within_limits(
+        &mut self,
+        left_start: usize,
+        left_end: usize,
+        right_start: usize,
+        right_end: usize,
+        limits: &mut Limits<'_>,
+    ) -> Search {
+        let left = &self.left[left_start..left_end];
+        let right = &self.right[right_start..right_end];
+        let eq = &mut self.eq;
+        let furthest = &mut self.furthest;
+
+        let left_len = left.len() as isize;
+        let right_len = right.len() as isize;
+        let max_depth = left_len + right_len;
+
+        for d in 0..=max_depth {
+            if d > 0 && limits.is_past_deadline() {
+                return Search::Abandoned;
+            }
+            if limits.is_too_expensive(d as usize) {
+                let (x, y) = (-(d - 1)..d)
+                    .step_by(2)
+                    .map(|k| (furthest[k], furthest[k] - k))
+                    .filter(|&(x, y)| x <= left_len && y <= right_len)
+                    .max_by_key(|&(x, y)| x + y)
+                    .unwrap_or_default();
+                return Search::Incomplete {
+                    x: left_start + x as usize,
+                    y: right_start + y as usize,
+                };
+            }
+
+            for k in (-d..=d).step_by(2) {
+                let mut x = if d == 0 {
+                    0
+                } else if k == -d || (k != d && furthest[k - 1] < furthest[k + 1]) {
+                    furthest[k + 1]
+                } else {
+                    furthest[k - 1] + 1
+                };
+                let mut y = x - k;
+
+                #[allow(clippy::suspicious_operation_groupings)]
+                while x < left_len && y < right_len && eq(&left[x as usize], &right[y as usize]) {
+                    x += 1;
+                    y += 1;
+                }
+
+                furthest[k] = x;
+
+                if x >= left_len && y >= right_len {
+                    return Search::Complete { depth: d as usize };
+                }
+            }
+        }
+
+        unreachable!(
+            "length of a path is longer than the maximum, which is `left.len() + right.len()`"
+        )
+    }
+}
+
+/// Values per diagonal *k* for the depth *d* currently examined.
+///
+/// The diagonal *k* ranges from *-(D + 1)* to *D + 1*, where *D* is the max
+/// depth.
+pub struct Vector {
+    data: Vec<isize>,
+    offset: isize,
+}
+
+impl Vector {
+    /// Allocates the slots for all diagonals up to the given max depth.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn try_new(max_depth: usize) -> Result<Self, TryReserveError> {
+        let len = 2 * max_depth + 3;
+        let mut data = Vec::new();
+        data.try_reserve_exact(len)?;
+        data.resize(len, 0);
+        Ok(Self {
+            data,
+            offset: max_depth as isize + 1,
+        })
+    }
+}
+
+impl Index<isize> for Vector {
+    type Output = isize;
+
+    #[allow(clippy::cast_sign_loss)]
+    fn index(&self, k: isize) -> &Self::Output {
+        &self.data[(k + self.offset) as usize]
+    }
+}
+
+impl IndexMut<isize> for Vector {
+    #[allow(clippy::cast_sign_loss)]
+    fn index_mut(&mut self, k: isize) -> &mut Self::Output {
+        &mut self.data[(k + self.offset) as usize]
+    }
+}
+
+/// Passes the diffs in ascending order on to a [`DiffHook`] and merges
+/// adjacent diffs of the same kind.
+///
+/// Within a run of differences the [`Diff::Left`] is always passed on before
+/// the [`Diff::Right`]. Only the diffs of the current run are held back until
+/// the run ends.
+pub struct DiffList<H> {
+    hook: H,
+    both: Option<(usize, usize, usize)>,
+    left: Option<(usize, usize)>,
+    right: Option<(usize, usize)>,
+}
+
+impl Default for DiffList<Capture> {
+    fn default() -> Self {
+        Self::new(Capture::new())
+    }
+}
+
+impl DiffList<Capture> {
+    pub fn into_vec(self) -> Vec<Diff> {
+        self.finish().into_diffs()
+    }
+}
+
+impl<H> DiffList<H>
+where
+    H: DiffHook,
+{
+    pub const fn new(hook: H) -> Self {
+        Self {
+            hook,
+            both: None,
+            left: None,
+            right: None,
+        }
+    }
+
+    pub fn push_both(&mut self, left_index: usize, right_index: usize, length: usize) {
+        if length == 0 {
+            return;
+        }
+        self.flush_changes();
+        match &mut self.both {
+            Some((_, _, last_length)) => *last_length += length,
+            None => self.both = Some((left_index, right_index, length)),
+        }
+    }
+
+    pub fn push_left(&mut self, index: usize, length: usize) {
+        if length == 0 {
+            return;
+        }
+        self.flush_both();
+        match &mut self.left {
+            Some((_, last_length)) => *last_length += length,
+            None => self.left = Some((index, length)),
+        }
+    }
+
+    pub fn push_right(&mut self, index: usize, length: usize) {
+        if length == 0 {
+            return;
+        }
+        self.flush_both();
+        match &mut self.right {
+            Some((_, last_length)) => *last_length += length,
+            None => self.right = Some((index, length)),
+        }
+    }
+
+    /// Appends the given diffs, whose indices are shifted by the given
+    /// offsets.
+    pub fn extend(&mut self, diffs: &[Diff], left_offset: usize, right_offset: usize) {
+        for diff in diffs {
+            match *diff {
+                Diff::Left { index, length } => self.push_left(left_offset + index, length),
+                Diff::Both {
+                    left_index,
+                    right_index,
+                    length,
+                } => self.push_both(left_offset + left_index, right_offset + right_index, length),
+                Diff::Right { index, length } => self.push_right(right_offset + index, length),
+            }
+        }
+    }
+
+    /// Passes on the diffs held back and finishes the hook.
+    pub fn finish(mut self) -> H {
+        self.flush_both();
+        self.flush_changes();
+        self.hook.finish();
+        self.hook
+    }
+
+    fn flush_both(&mut self) {
+        if let Some((left_index, right_index, length)) = self.both.take() {
+            self.hook.equal(left_index, right_index, length);
+        }
+    }
+
+    fn flush_changes(&mut self) {
+        if let Some((index, length)) = self.left.take() {
+            self.hook.delete(index, length);
+        }
+        if let Some((index, length)) = self.right.take() {
+            self.hook.insert(index, length);
+        }
+    }
+}
+
+/// Length of the common prefix of two slices.
+pub fn common_prefix_len<T, F>(left: &[T], right: &[T], eq: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    left.iter()
+        .zip(right)
+        .take_while(|(left, right)| eq(left, right))
+        .count()
+}
+
+/// Length of the common suffix of two slices.
+pub fn common_suffix_len<T, F>(left: &[T], right: &[T], eq: &mut F) -> usize
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    left.iter()
+        .rev()
+        .zip(right.iter().rev())
+        .take_while(|(left, right)| eq(left, right))
+        .count()
+}
+
+#[cfg(all(test, feature = "std"))]
+pub mod reference;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,353 @@
+use super::*;
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use crate::assertions::assert_diffs_transform;
+    use crate::myers::reference::{find_shortest_trace, list_diffs};
+    use proptest::prelude::*;
+
+    /// Lists the diffs by the original algorithm for the part between the
+    /// common prefix and the common suffix.
+    fn expected_diffs(left: &[u8], right: &[u8]) -> Vec<Diff> {
+        let prefix_len = common_prefix_len(left, right, &mut PartialEq::eq);
+        let suffix_len = common_suffix_len(
+            &left[prefix_len..],
+            &right[prefix_len..],
+            &mut PartialEq::eq,
+        );
+        let left_end = left.len() - suffix_len;
+        let right_end = right.len() - suffix_len;
+        let left_middle = &left[prefix_len..left_end];
+        let right_middle = &right[prefix_len..right_end];
+
+        let trace = find_shortest_trace(left_middle, right_middle);
+        let middle_diffs = list_diffs(left_middle, right_middle, &trace);
+
+        let mut diffs = DiffList::default();
+        diffs.push_both(0, 0, prefix_len);
+        for diff in middle_diffs {
+            match diff {
+                Diff::Left { index, length } => diffs.push_left(prefix_len + index, length),
+                Diff::Both {
+                    left_index,
+                    right_index,
+                    length,
+                } => diffs.push_both(prefix_len + left_index, prefix_len + right_index, length),
+                Diff::Right { index, length } => diffs.push_right(prefix_len + index, length),
+            }
+        }
+        diffs.push_both(left_end, right_end, suffix_len);
+        let diffs = diffs.into_vec();
+        if diffs.is_empty() {
+            list_diffs(left, right, &trace)
+        } else {
+            diffs
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn the_linear_space_variant_lists_the_same_diffs_as_the_original_algorithm_between_common_prefix_and_suffix(
+            left in prop::collection::vec(0..4_u8, 0..=200),
+            right in prop::collection::vec(0..4_u8, 0..=200),
+        ) {
+            let expected = expected_diffs(&left, &right);
+
+            let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}"));
+
+            prop_assert_eq!(diffs, expected);
+        }
+
+        #[test]
+        fn the_diffs_cover_both_sequences_in_ascending_order(
+            left in prop::collection::vec(0..4_u8, 0..=200),
+            right in prop::collection::vec(0..4_u8, 0..=200),
+        ) {
+            let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}"));
+
+            let mut left_pos = 0;
+            let mut right_pos = 0;
+            for diff in &diffs {
+                match *diff {
+                    Diff::Left { index, length } => {
+                        prop_assert_eq!(index, left_pos);
+                        left_pos += length;
+                    },
+                    Diff::Both { left_index, right_index, length } => {
+                        prop_assert_eq!(left_index, left_pos);
+                        prop_assert_eq!(right_index, right_pos);
+                        prop_assert_eq!(
+                            &left[left_index..left_index + length],
+                            &right[right_index..right_index + length]
+                        );
+                        left_pos += length;
+                        right_pos += length;
+                    },
+                    Diff::Right { index, length } => {
+                        prop_assert_eq!(index, right_pos);
+                        right_pos += length;
+                    },
+                }
+            }
+            prop_assert_eq!(left_pos, left.len());
+            prop_assert_eq!(right_pos, right.len());
+        }
+
+        #[test]
+        fn the_diffs_within_a_max_cost_transform_left_into_right(
+            left in prop::collection::vec(0..4_u8, 0..=200),
+            right in prop::collection::vec(0..4_u8, 0..=200),
+            max_cost in 0..20_usize,
+        ) {
+            let mut limits = Limits::new().max_cost(max_cost);
+
+            let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut limits)
+                .unwrap_or_else(|err| panic!("{err}"));
+
+            assert_diffs_transform(&left, &right, &diffs)?;
+        }
+
+        #[test]
+        fn the_diffs_within_a_max_cost_not_exceeded_are_the_same_as_without_limits(
+            left in prop::collection::vec(0..4_u8, 0..=100),
+            right in prop::collection::vec(0..4_u8, 0..=100),
+        ) {
+            let mut limits = Limits::new().max_cost(left.len() + right.len());
+
+            let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut limits)
+                .unwrap_or_else(|err| panic!("{err}"));
+
+            prop_assert_eq!(diffs, diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}")));
+        }
+    }
+}
+
+#[test]
+fn diff_of_long_sequences_with_few_differences() {
+    let left = (0..20_000).collect::<Vec<_>>();
+    let mut right = left.clone();
+    right.remove(10_000);
+    right.insert(15_000, -1);
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new())
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 10_000,
+        },
+        Diff::Left {
+            index: 10_000,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 10_001,
+            right_index: 10_000,
+            length: 5_000,
+        },
+        Diff::Right {
+            index: 15_000,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 15_001,
+            right_index: 15_001,
+            length: 4_999,
+        },
+    ]);
+}
+
+#[test]
+fn common_prefix_and_suffix_are_listed_as_both() {
+    let left = [1, 2, 3, 4, 5, 6, 7];
+    let right = [1, 2, 4, 3, 5, 6, 7];
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new())
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Left {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 2,
+            length: 1,
+        },
+        Diff::Right {
+            index: 3,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 4,
+            right_index: 4,
+            length: 3,
+        },
+    ]);
+}
+
+#[test]
+fn the_common_suffix_is_stripped_before_searching_the_differences() {
+    let left = [1, 2];
+    let right = [3, 2];
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new())
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 1,
+        },
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 1,
+            right_index: 1,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn diff_of_long_sequences_with_nothing_in_common() {
+    let left = (0..3_000).collect::<Vec<_>>();
+    let right = (3_000..5_000).collect::<Vec<_>>();
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new())
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 3_000,
+        },
+        Diff::Right {
+            index: 0,
+            length: 2_000,
+        },
+    ]);
+}
+
+#[test]
+fn diff_exceeding_the_max_cost_continues_from_the_furthest_point() {
+    let left = [1, 2, 3, 4, 5, 6, 7, 8];
+    let right = [0, 2, 3, 9, 5, 6, 0, 8];
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new().max_cost(2))
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 1,
+        },
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 1,
+            right_index: 1,
+            length: 2,
+        },
+        Diff::Left {
+            index: 3,
+            length: 1,
+        },
+        Diff::Right {
+            index: 3,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 4,
+            right_index: 4,
+            length: 2,
+        },
+        Diff::Left {
+            index: 6,
+            length: 1,
+        },
+        Diff::Right {
+            index: 6,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 7,
+            right_index: 7,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn diff_with_a_max_cost_of_zero_lists_the_part_between_prefix_and_suffix_as_replaced() {
+    let left = [1, 2, 3, 4, 5];
+    let right = [1, 3, 2, 4, 5];
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut Limits::new().max_cost(0))
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 2,
+        },
+        Diff::Right {
+            index: 1,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 3,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn diff_exceeding_the_deadline_lists_all_remaining_elements_as_replaced() {
+    let left = (0..3_000).map(|n| n % 7).collect::<Vec<_>>();
+    let right = (0..3_000).map(|n| n % 11).collect::<Vec<_>>();
+    let mut checks = 0;
+    let mut limits = Limits::new().max_cost(8).deadline(|| {
+        checks += 1;
+        checks > 40
+    });
+
+    let diffs = diff_within_limits(&left, &right, PartialEq::eq, &mut limits)
+        .unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(diffs[diffs.len() - 3..].to_vec()).contains_exactly([
+        Diff::Both {
+            left_index: 56,
+            right_index: 88,
+            length: 7,
+        },
+        Diff::Left {
+            index: 63,
+            length: 2_937,
+        },
+        Diff::Right {
+            index: 95,
+            length: 2_905,
+        },
+    ]);
+}
@@ -0,0 +1,304 @@
+//! The *O(NP)* sequence comparison algorithm by Sun Wu, Udi Manber, Gene
+//! Myers and Webb Miller.
+//!
+//! Like the Myers algorithm, it searches the shortest path through the edit
+//! graph, but it only counts the deletions *P* from the shorter sequence
+//! instead of all differences *D*. The number of insertions is at least the
+//! difference of the lengths of the two sequences, which the algorithm does
+//! not need to explore. This makes it much faster than the Myers algorithm if
+//! one sequence is much longer than the other.
+//!
+//! Only the furthest point on each diagonal is kept, together with the
+//! point where the path to it crosses the middle of the edit graph. The
+//! crossing point of the shortest path splits the edit graph into two
+//! smaller ones, which are searched recursively. This needs memory linear in
+//! the lengths of the two sequences.
+//!
+//! Before searching the edit graph, the common prefix and the common suffix
+//! of the two sequences are stripped off.
+//!
+//! See the [paper by Wu, Manber, Myers and Miller].
+//!
+//! [paper by Wu, Manber, Myers and Miller]: https://doi.org/10.1016/0020-0190(90)90035-V
+
+use crate::myers::{self, common_prefix_len, common_suffix_len, DiffList, Vector};
+use crate::std::{collections::TryReserveError, ops::Range, vec, vec::Vec};
+use crate::{Capture, Diff, DiffError, Limits};
+
+/// Find the common subsequences and differences between two slices using
+/// the *O(NP)* algorithm.
+///
+/// Two elements are considered equal if the function `eq` returns `true`.
+///
+/// If the limits are exceeded while searching the differences between two
+/// subsequences, the diffs of these subsequences are approximated by the
+/// Myers algorithm, see [`Limits`]. The common subsequences found before are
+/// kept.
+///
+/// Returns an error if the memory needed for the search can not be
+/// allocated.
+pub fn diff<T, F>(
+    left: &[T],
+    right: &[T],
+    eq: F,
+    limits: &mut Limits<'_>,
+) -> Result<Vec<Diff>, DiffError>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    if left.len() + right.len() == 0 {
+        return Ok(vec![Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 0,
+        }]);
+    }
+
+    let mut onp = Onp::try_new(left, right, eq)?;
+    let mut diffs = DiffList::default();
+    onp.diff_ranges(0..left.len(), 0..right.len(), limits, &mut diffs)?;
+    Ok(diffs.into_vec())
+}
+
+/// State of the search through the edit graph of the sequences `left` and
+/// `right`.
+///
+/// The search runs on the edit graph of a shorter sequence *A* and a longer
+/// sequence *B*, which are subsequences of `left` and `right` or the other
+/// way round.
+struct Onp<'a, T, F> {
+    left: &'a [T],
+    right: &'a [T],
+    eq: F,
+    /// The furthest point on each diagonal *k = y - x*, given by its *y*.
+    furthest: Vector,
+    /// The start of the first snake on the furthest reaching path of each
+    /// diagonal that ends in the second half of the edit graph, given by its
+    /// *x* and *y*.
+    middle_x: Vector,
+    middle_y: Vector,
+}
+
+/// The subsequences *A* and *B* that span the edit graph currently searched.
+struct EditGraph {
+    a: Range<usize>,
+    b: Range<usize>,
+    /// Whether *A* is a subsequence of the right sequence and *B* one of the
+    /// left sequence.
+    swapped: bool,
+}
+
+impl<'a, T, F> Onp<'a, T, F>
+where
+    F: FnMut(&T, &T) -> bool,
+{
+    /// Allocates the slots for all diagonals of the largest edit graph.
+    fn try_new(left: &'a [T], right: &'a [T], eq: F) -> Result<Self, TryReserveError> {
+        let max_depth = left.len() + right.len();
+        Ok(Self {
+            left,
+            right,
+            eq,
+            furthest: Vector::try_new(max_depth)?,
+            middle_x: Vector::try_new(max_depth)?,
+            middle_y: Vector::try_new(max_depth)?,
+        })
+    }
+
+    /// Lists the diffs of the given ranges of the left and the right
+    /// sequence.
+    ///
+    /// The diffs of ranges whose search exceeds the limits are approximated
+    /// by the Myers algorithm.
+    fn diff_ranges(
+        &mut self,
+        left: Range<usize>,
+        right: Range<usize>,
+        limits: &mut Limits<'_>,
+        diffs: &mut DiffList<Capture>,
+    ) -> Result<(), DiffError> {
+        let prefix_len = common_prefix_len(
+            &self.left[left.clone()],
+            &self.right[right.clone()],
+            &mut self.eq,
+        );
+        diffs.push_both(left.start, right.start, prefix_len);
+        let left = left.start + prefix_len..left.end;
+        let right = right.start + prefix_len..right.end;
+        let suffix_len = common_suffix_len(
+            &self.left[left.clone()],
+            &self.right[right.clone()],
+            &mut self.eq,
+        );
+        let left = left.start..left.end - suffix_len;
+        let right = right.start..right.end - suffix_len;
+
+        if left.is_empty() || right.is_empty() {
+            diffs.push_left(left.start, left.len());
+            diffs.push_right(right.start, right.len());
+        } else if let Some((left_middle, right_middle)) =
+            self.find_middle(left.clone(), right.clone(), limits)
+        {
+            self.diff_ranges(
+                left.start..left_middle,
+                right.start..right_middle,
+                limits,
+                diffs,
+            )?;
+            self.diff_ranges(
+                left_middle..left.end,
+                right_middle..right.end,
+                limits,
+                diffs,
+            )?;
+        } else {
+            let approximation = myers::diff_within_limits(
+                &self.left[left.clone()],
+                &self.right[right.clone()],
+                &mut self.eq,
+                limits,
+            )?;
+            diffs.extend(&approximation, left.start, right.start);
+        }
+        diffs.push_both(left.end, right.end, suffix_len);
+        Ok(())
+    }
+
+    /// Searches the shortest path through the edit graph of the given
+    /// non-empty ranges.
+    ///
+    /// Returns the point where the path enters the second half of the edit
+    /// graph, as indices into the left and the right sequence, or `None` if
+    /// the limits are exceeded. The point is neither at the start nor at the
+    /// end of the ranges, if they have no common prefix.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    fn find_middle(
+        &mut self,
+        left: Range<usize>,
+        right: Range<usize>,
+        limits: &mut Limits<'_>,
+    ) -> Option<(usize, usize)> {
+        // The algorithm requires the sequence A to be the shorter one.
+        let graph = if left.len() > right.len() {
+            EditGraph {
+                a: right,
+                b: left,
+                swapped: true,
+            }
+        } else {
+            EditGraph {
+                a: left,
+                b: right,
+                swapped: false,
+            }
+        };
+        let m = graph.a.len() as isize;
+        let n = graph.b.len() as isize;
+        let delta = n - m;
+
+        for k in -1..=delta + 1 {
+            self.furthest[k] = -1;
+        }
+        let mut p = 0;
+        loop {
+            if limits.is_too_expensive((delta + 2 * p) as usize) || limits.is_past_deadline() {
+                return None;
+            }
+            if p > 0 {
+                self.furthest[-p - 1] = -1;
+                self.furthest[delta + p + 1] = -1;
+            }
+            for k in -p..delta {
+                self.search_diagonal(&graph, k, p == 0 && k == 0);
+            }
+            for k in (delta + 1..=delta + p).rev() {
+                self.search_diagonal(&graph, k, false);
+            }
+            self.search_diagonal(&graph, delta, p == 0 && delta == 0);
+            if self.furthest[delta] == n {
+                break;
+            }
+            p += 1;
+        }
+
+        let a_middle = graph.a.start + self.middle_x[delta] as usize;
+        let b_middle = graph.b.start + self.middle_y[delta] as usize;
+        Some(if graph.swapped {
+            (b_middle, a_middle)
+        } else {
+            (a_middle, b_middle)
+        })
+    }
+
+    /// Extends the furthest reaching path on the diagonal `k` by one
+    /// difference followed by a snake.
+    ///
+    /// An insertion moves down from the diagonal *k - 1*, a deletion moves
+    /// right from the diagonal *k + 1*. Moves that leave the edit graph are
+    /// not considered. The very first snake starts at the `origin` of the
+    /// edit graph.
+    #[allow(
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss,
+        clippy::many_single_char_names
+    )]
+    fn search_diagonal(&mut self, graph: &EditGraph, k: isize, origin: bool) {
+        let m = graph.a.len() as isize;
+        let n = graph.b.len() as isize;
+
+        let insertion = if origin {
+            Some((0, None))
+        } else if 0 <= self.furthest[k - 1] && self.furthest[k - 1] < n {
+            Some((self.furthest[k - 1] + 1, Some(k - 1)))
+        } else {
+            None
+        };
+        let deletion = if 0 <= self.furthest[k + 1] && self.furthest[k + 1] - k <= m {
+            Some((self.furthest[k + 1], Some(k + 1)))
+        } else {
+            None
+        };
+        let (y, previous) = match (insertion, deletion) {
+            (Some(insertion), Some(deletion)) if insertion.0 > deletion.0 => insertion,
+            (_, Some(deletion)) => deletion,
+            (Some(insertion), None) => insertion,
+            (None, None) => return,
+        };
+
+        let x = y - k;
+        let mut length = 0;
+        while x + length < m
+            && y + length < n
+            && self.snake_continues(graph, x + length, y + length)
+        {
+            length += 1;
+        }
+
+        let (middle_x, middle_y) = match previous {
+            Some(previous) if self.middle_x[previous] >= 0 => {
+                (self.middle_x[previous], self.middle_y[previous])
+            },
+            _ if 2 * (2 * (y + length) - k) >= m + n => (x, y),
+            _ => (-1, -1),
+        };
+        self.furthest[k] = y + length;
+        self.middle_x[k] = middle_x;
+        self.middle_y[k] = middle_y;
+    }
+
+    /// Returns whether the element at `x` in the sequence *A* equals the one
+    /// at `y` in the sequence *B*.
+    #[allow(clippy::cast_sign_loss)]
+    fn snake_continues(&mut self, graph: &EditGraph, x: isize, y: isize) -> bool {
+        let x = graph.a.start + x as usize;
+        let y = graph.b.start + y as usize;
+        if graph.swapped {
+            (self.eq)(&self.left[y], &self.right[x])
+        } else {
+            (self.eq)(&self.left[x], &self.right[y])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
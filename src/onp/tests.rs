@@ -0,0 +1,185 @@
+use super::*;
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use crate::assertions::assert_diffs_transform;
+    use crate::{apply, diff as diff_myers, Diff};
+    use proptest::prelude::*;
+
+    fn diff_count(diffs: &[Diff]) -> usize {
+        diffs
+            .iter()
+            .map(|diff| match diff {
+                Diff::Left { length, .. } | Diff::Right { length, .. } => *length,
+                Diff::Both { .. } => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn onp_diff_of_a_much_longer_sequence_is_minimal() {
+        let left: Vec<u8> = (0..300_u16).map(|i| (i * 7 % 5) as u8).collect();
+        let right: Vec<u8> = (0..3000_u16).map(|i| (i * 13 % 7) as u8).collect();
+
+        let diffs = diff(&left, &right, PartialEq::eq, &mut Limits::new())
+            .unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(apply(&left, &right, &diffs)).has_value(right.clone());
+        assert_that!(diff_count(&diffs)).is_equal_to(diff_count(&diff_myers(&left, &right)));
+    }
+
+    proptest! {
+        #[test]
+        fn onp_diffs_transform_left_into_right(
+            left in prop::collection::vec(0..4_u8, 0..=60),
+            right in prop::collection::vec(0..4_u8, 0..=60),
+        ) {
+            let diffs = diff(&left, &right, PartialEq::eq, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}"));
+
+            assert_diffs_transform(&left, &right, &diffs)?;
+        }
+
+        #[test]
+        fn onp_diffs_within_a_deadline_transform_left_into_right(
+            left in prop::collection::vec(0..4_u8, 0..=100),
+            right in prop::collection::vec(0..4_u8, 0..=100),
+            max_checks in 0..20_usize,
+        ) {
+            let mut checks = 0;
+            let mut limits = Limits::new().deadline(move || {
+                checks += 1;
+                checks > max_checks
+            });
+
+            let diffs = diff(&left, &right, PartialEq::eq, &mut limits).unwrap_or_else(|err| panic!("{err}"));
+
+            assert_diffs_transform(&left, &right, &diffs)?;
+        }
+
+        #[test]
+        fn onp_diffs_are_minimal(
+            left in prop::collection::vec(0..4_u8, 0..=60),
+            right in prop::collection::vec(0..4_u8, 0..=60),
+        ) {
+            let diffs = diff(&left, &right, PartialEq::eq, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}"));
+
+            prop_assert_eq!(diff_count(&diffs), diff_count(&diff_myers(&left, &right)));
+        }
+    }
+}
+
+#[test]
+fn onp_diff_of_two_empty_sequences() {
+    let diffs = diff::<u8, _>(&[], &[], PartialEq::eq, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 0,
+    }]);
+}
+
+#[test]
+fn onp_diff_of_a_short_and_a_long_sequence() {
+    let left = ['b', 'd'];
+    let right = ['a', 'b', 'c', 'd', 'e'];
+
+    let diffs = diff(&left, &right, PartialEq::eq, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 0,
+            right_index: 1,
+            length: 1,
+        },
+        Diff::Right {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 1,
+            right_index: 3,
+            length: 1,
+        },
+        Diff::Right {
+            index: 4,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn onp_diff_of_a_long_and_a_short_sequence() {
+    let left = ['a', 'b', 'c', 'd', 'e'];
+    let right = ['x', 'c'];
+
+    let diffs = diff(&left, &right, PartialEq::eq, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Left {
+            index: 0,
+            length: 2,
+        },
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 1,
+            length: 1,
+        },
+        Diff::Left {
+            index: 3,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn onp_diff_exceeding_the_deadline_keeps_the_common_subsequences_found_before() {
+    let left = [1, 2, 3, 4, 5, 6, 7, 8];
+    let right = [0, 2, 3, 9, 5, 6, 0, 8];
+    let mut checks = 0;
+    let mut limits = Limits::new().deadline(move || {
+        checks += 1;
+        checks > 6
+    });
+
+    let diffs = diff(&left, &right, PartialEq::eq, &mut limits);
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Left {
+            index: 0,
+            length: 1,
+        },
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 1,
+            right_index: 1,
+            length: 2,
+        },
+        Diff::Left {
+            index: 3,
+            length: 4,
+        },
+        Diff::Right {
+            index: 3,
+            length: 4,
+        },
+        Diff::Both {
+            left_index: 7,
+            right_index: 7,
+            length: 1,
+        },
+    ]);
+}
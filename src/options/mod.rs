@@ -0,0 +1,339 @@
+//! Configuration of how the differences are found, like the algorithm, the
+//! limits of the search and the cleanup of the result.
+
+use crate::lines::lines;
+use crate::std::vec::Vec;
+use crate::{
+    check_sequence_length, cleanup_efficiency, cleanup_semantic, diff_by_within_limits,
+    diff_within_limits, Algorithm, Deadline, Diff, DiffError, Limits, Side, Tokenizer,
+    WordBoundaryTokenizer,
+};
+
+/// Finds the differences of two sequences or strings as configured.
+///
+/// The options are set by the builder methods. By default, the differences
+/// are found by [`Algorithm::Myers`] without any limits and without cleanup,
+/// and strings are compared `char` by `char`. The functions [`diff()`],
+/// [`diff_by()`] and [`diff_str()`] find the differences with the default
+/// options.
+///
+/// [`diff()`]: crate::diff
+/// [`diff_by()`]: crate::diff_by
+/// [`diff_str()`]: crate::diff_str
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{Algorithm, Cleanup, Diff, DiffOptions, Granularity};
+///
+/// let left = "lorem ipsum dolor";
+/// let right = "lorem dolor sit";
+///
+/// let diffs = DiffOptions::new()
+///     .algorithm(Algorithm::Histogram)
+///     .granularity(Granularity::Words)
+///     .cleanup(Cleanup::Semantic)
+///     .diff_str(left, right);
+///
+/// assert_eq!(
+///     diffs,
+///     [
+///         Diff::Both { left_index: 0, right_index: 0, length: 2 },
+///         Diff::Left { index: 2, length: 3 },
+///         Diff::Right { index: 2, length: 3 },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct DiffOptions<'a> {
+    algorithm: Algorithm,
+    limits: Limits<'a>,
+    cleanup: Cleanup,
+    granularity: Granularity,
+}
+
+impl<'a> DiffOptions<'a> {
+    /// Creates the default options.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            algorithm: Algorithm::Myers,
+            limits: Limits::new(),
+            cleanup: Cleanup::None,
+            granularity: Granularity::Chars,
+        }
+    }
+
+    /// Sets the algorithm used to find the differences.
+    ///
+    /// By default, the algorithm is [`Algorithm::Myers`].
+    #[must_use]
+    pub const fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Sets the max number of differences the search explores, before it
+    /// continues with an approximation. See [`Limits::max_cost()`].
+    #[must_use]
+    pub fn max_cost(mut self, max_cost: usize) -> Self {
+        self.limits = self.limits.max_cost(max_cost);
+        self
+    }
+
+    /// Sets the deadline after which the search stops exploring. See
+    /// [`Limits::deadline()`].
+    ///
+    /// The deadline is not reset between two diffs found with the same
+    /// options.
+    #[must_use]
+    pub fn deadline(mut self, deadline: impl Deadline + 'a) -> Self {
+        self.limits = self.limits.deadline(deadline);
+        self
+    }
+
+    /// Sets the cleanup applied to the diffs after they have been found.
+    ///
+    /// By default, the diffs are not cleaned up.
+    #[must_use]
+    pub const fn cleanup(mut self, cleanup: Cleanup) -> Self {
+        self.cleanup = cleanup;
+        self
+    }
+
+    /// Sets the units in which strings are compared by
+    /// [`diff_str()`](Self::diff_str).
+    ///
+    /// By default, strings are compared `char` by `char`.
+    #[must_use]
+    pub const fn granularity(mut self, granularity: Granularity) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// Find the common subsequences and differences between two slices.
+    ///
+    /// The elements only need to be comparable for equality, like for
+    /// [`diff()`]. [`Algorithm::Patience`] and [`Algorithm::Histogram`] look
+    /// up the elements by their order, so they can not be used here. Use
+    /// [`diff_ord_slices()`] to find the differences of `Ord` elements by
+    /// these algorithms.
+    ///
+    /// Each of the two slices must not be longer than the max supported
+    /// length [`max_sequence_length()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the algorithm needs to order the elements, if one of the
+    /// slices is longer than the max supported length or if the memory needed
+    /// to find the differences can not be allocated. See
+    /// [`try_diff_slices()`](Self::try_diff_slices) for a variant that returns
+    /// an error instead.
+    ///
+    /// [`diff()`]: crate::diff
+    /// [`diff_ord_slices()`]: Self::diff_ord_slices
+    /// [`max_sequence_length()`]: crate::max_sequence_length
+    #[must_use]
+    pub fn diff_slices<T>(&mut self, left: &[T], right: &[T]) -> Vec<Diff>
+    where
+        T: PartialEq,
+    {
+        self.try_diff_slices(left, right)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Find the common subsequences and differences between two slices.
+    ///
+    /// This is the fallible variant of [`diff_slices()`](Self::diff_slices).
+    pub fn try_diff_slices<T>(&mut self, left: &[T], right: &[T]) -> Result<Vec<Diff>, DiffError>
+    where
+        T: PartialEq,
+    {
+        self.try_diff_slices_by(left, right, PartialEq::eq)
+    }
+
+    /// Find the common subsequences and differences between two slices using
+    /// the given function to compare elements.
+    ///
+    /// Two elements are considered equal if the function `eq` returns `true`,
+    /// like for [`diff_by()`]. [`Algorithm::Patience`] and
+    /// [`Algorithm::Histogram`] can not be used here, as they need to order
+    /// the elements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the algorithm needs to order the elements, if one of the
+    /// slices is longer than the max supported length
+    /// [`max_sequence_length()`] or if the memory needed to find the
+    /// differences can not be allocated. See
+    /// [`try_diff_slices_by()`](Self::try_diff_slices_by) for a variant that
+    /// returns an error instead.
+    ///
+    /// [`diff_by()`]: crate::diff_by
+    /// [`max_sequence_length()`]: crate::max_sequence_length
+    #[must_use]
+    pub fn diff_slices_by<T, F>(&mut self, left: &[T], right: &[T], eq: F) -> Vec<Diff>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        self.try_diff_slices_by(left, right, eq)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Find the common subsequences and differences between two slices using
+    /// the given function to compare elements.
+    ///
+    /// This is the fallible variant of
+    /// [`diff_slices_by()`](Self::diff_slices_by).
+    pub fn try_diff_slices_by<T, F>(
+        &mut self,
+        left: &[T],
+        right: &[T],
+        eq: F,
+    ) -> Result<Vec<Diff>, DiffError>
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        check_sequence_length(Side::Left, left.len())?;
+        check_sequence_length(Side::Right, right.len())?;
+
+        let mut diffs = diff_by_within_limits(left, right, eq, self.algorithm, &mut self.limits)?;
+        self.clean_up(&mut diffs);
+        Ok(diffs)
+    }
+
+    /// Find the common subsequences and differences between two slices of
+    /// elements that are `Ord`.
+    ///
+    /// Unlike [`diff_slices()`](Self::diff_slices), all algorithms can be
+    /// used, including the ones that look up the elements by their order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the slices is longer than the max supported length
+    /// [`max_sequence_length()`] or if the memory needed to find the
+    /// differences can not be allocated. See
+    /// [`try_diff_ord_slices()`](Self::try_diff_ord_slices) for a variant
+    /// that returns an error instead.
+    ///
+    /// [`max_sequence_length()`]: crate::max_sequence_length
+    #[must_use]
+    pub fn diff_ord_slices<T>(&mut self, left: &[T], right: &[T]) -> Vec<Diff>
+    where
+        T: Ord,
+    {
+        self.try_diff_ord_slices(left, right)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Find the common subsequences and differences between two slices of
+    /// elements that are `Ord`.
+    ///
+    /// This is the fallible variant of
+    /// [`diff_ord_slices()`](Self::diff_ord_slices).
+    pub fn try_diff_ord_slices<T>(
+        &mut self,
+        left: &[T],
+        right: &[T],
+    ) -> Result<Vec<Diff>, DiffError>
+    where
+        T: Ord,
+    {
+        check_sequence_length(Side::Left, left.len())?;
+        check_sequence_length(Side::Right, right.len())?;
+
+        let mut diffs = diff_within_limits(left, right, self.algorithm, &mut self.limits)?;
+        self.clean_up(&mut diffs);
+        Ok(diffs)
+    }
+
+    /// Applies the configured cleanup to the diffs.
+    fn clean_up(&self, diffs: &mut Vec<Diff>) {
+        match self.cleanup {
+            Cleanup::None => {},
+            Cleanup::Semantic => cleanup_semantic(diffs),
+            Cleanup::Efficiency { edit_cost } => cleanup_efficiency(diffs, edit_cost),
+        }
+    }
+
+    /// Find the common subsequences and differences between two strings.
+    ///
+    /// The strings are split into units according to the
+    /// [granularity](Self::granularity). The indices and lengths of the
+    /// returned [`Diff`]s count these units.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the strings has more units than the max supported
+    /// length [`max_sequence_length()`] or if the memory needed to find the
+    /// differences can not be allocated. See
+    /// [`try_diff_str()`](Self::try_diff_str) for a variant that returns an
+    /// error instead.
+    ///
+    /// [`max_sequence_length()`]: crate::max_sequence_length
+    #[must_use]
+    pub fn diff_str(&mut self, left: &str, right: &str) -> Vec<Diff> {
+        self.try_diff_str(left, right)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Find the common subsequences and differences between two strings.
+    ///
+    /// This is the fallible variant of [`diff_str()`](Self::diff_str).
+    pub fn try_diff_str(&mut self, left: &str, right: &str) -> Result<Vec<Diff>, DiffError> {
+        match self.granularity {
+            Granularity::Chars => self.try_diff_ord_slices(
+                &left.chars().collect::<Vec<_>>(),
+                &right.chars().collect::<Vec<_>>(),
+            ),
+            Granularity::Words => self.try_diff_ord_slices(
+                &WordBoundaryTokenizer.tokenize(left),
+                &WordBoundaryTokenizer.tokenize(right),
+            ),
+            Granularity::Lines => self.try_diff_ord_slices(&lines(left), &lines(right)),
+        }
+    }
+}
+
+/// The cleanup applied to diffs after they have been found.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Cleanup {
+    /// The diffs are returned as found.
+    #[default]
+    None,
+
+    /// The diffs are cleaned up by [`cleanup_semantic()`].
+    Semantic,
+
+    /// The diffs are cleaned up by [`cleanup_efficiency()`] with the given
+    /// edit cost, which is usually [`DEFAULT_EDIT_COST`].
+    ///
+    /// [`DEFAULT_EDIT_COST`]: crate::DEFAULT_EDIT_COST
+    Efficiency {
+        /// The cost of an edit operation in terms of elements.
+        edit_cost: usize,
+    },
+}
+
+/// The units in which two strings are compared.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    /// The strings are compared `char` by `char` like by [`diff_str()`].
+    ///
+    /// [`diff_str()`]: crate::diff_str
+    #[default]
+    Chars,
+
+    /// The strings are compared word by word like by [`diff_words()`].
+    ///
+    /// [`diff_words()`]: crate::diff_words
+    Words,
+
+    /// The strings are compared line by line like by [`diff_lines()`].
+    ///
+    /// [`diff_lines()`]: crate::diff_lines
+    Lines,
+}
+
+#[cfg(test)]
+mod tests;
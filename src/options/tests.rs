@@ -0,0 +1,294 @@
+use super::*;
+use crate::std::vec;
+use crate::{diff, diff_lines, diff_str, diff_words, max_sequence_length};
+use asserting::prelude::*;
+
+#[test]
+fn diff_slices_with_default_options_is_the_same_as_diff() {
+    let left = [1, 2, 3, 4, 5, 6];
+    let right = [1, 3, 2, 4, 6, 7];
+
+    let diffs = DiffOptions::new().diff_slices(&left, &right);
+
+    assert_that!(diffs).is_equal_to(diff(&left, &right));
+}
+
+#[test]
+fn default_options_are_the_same_as_new_options() {
+    let options = DiffOptions::default();
+
+    assert_that!(options.algorithm).is_equal_to(Algorithm::Myers);
+    assert_that!(options.cleanup).is_equal_to(Cleanup::None);
+    assert_that!(options.granularity).is_equal_to(Granularity::Chars);
+}
+
+#[test]
+fn diff_slices_of_elements_that_are_only_partial_eq() {
+    let left = [1.0, 2.0, 3.0, f64::NAN];
+    let right = [1.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+
+    let diffs = DiffOptions::new()
+        .algorithm(Algorithm::Auto)
+        .diff_slices(&left, &right);
+
+    assert_that!(diffs).is_equal_to(diff(&left, &right));
+}
+
+#[test]
+fn diff_slices_by_compares_elements_with_the_given_function() {
+    let left = [1.0, 2.0, 3.0];
+    let right = [1.0001, 2.5, 2.9999];
+
+    let diffs = DiffOptions::new().algorithm(Algorithm::Onp).diff_slices_by(
+        &left,
+        &right,
+        |a: &f64, b: &f64| (a - b).abs() < 0.001,
+    );
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 1,
+        },
+        Diff::Right {
+            index: 1,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 2,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn try_diff_slices_with_an_algorithm_ordering_the_elements_returns_an_error() {
+    let left = [2];
+    let right = [3, 2, 2];
+
+    let patience = DiffOptions::new()
+        .algorithm(Algorithm::Patience)
+        .try_diff_slices(&left, &right);
+    let histogram = DiffOptions::new()
+        .algorithm(Algorithm::Histogram)
+        .try_diff_slices_by(&left, &right, |a, b| a == b);
+
+    assert_that!(patience).has_error(DiffError::OrderRequired {
+        algorithm: Algorithm::Patience,
+    });
+    assert_that!(histogram).has_error(DiffError::OrderRequired {
+        algorithm: Algorithm::Histogram,
+    });
+}
+
+#[test]
+#[should_panic(expected = "the Histogram algorithm needs elements that are ordered")]
+fn diff_slices_with_an_algorithm_ordering_the_elements_panics() {
+    let _ = DiffOptions::new()
+        .algorithm(Algorithm::Histogram)
+        .diff_slices(&[1, 2], &[2, 3]);
+}
+
+#[test]
+fn diff_ord_slices_with_the_patience_algorithm() {
+    let left = ["a", "b", "c", "d"];
+    let right = ["c", "d", "a", "b"];
+
+    let diffs = DiffOptions::new()
+        .algorithm(Algorithm::Patience)
+        .diff_ord_slices(&left, &right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Right {
+            index: 2,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn diff_slices_with_a_max_cost_of_zero_lists_the_part_between_prefix_and_suffix_as_replaced() {
+    let left = ['a', 'b', 'c', 'd', 'e'];
+    let right = ['a', 'c', 'b', 'd', 'e'];
+
+    let diffs = DiffOptions::new().max_cost(0).diff_slices(&left, &right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 2,
+        },
+        Diff::Right {
+            index: 1,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 3,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn diff_slices_exceeding_the_deadline_lists_the_part_between_prefix_and_suffix_as_replaced() {
+    let left = ['a', 'b', 'c', 'd', 'e'];
+    let right = ['a', 'c', 'b', 'd', 'e'];
+
+    let diffs = DiffOptions::new()
+        .deadline(|| true)
+        .diff_slices(&left, &right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 2,
+        },
+        Diff::Right {
+            index: 1,
+            length: 2,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 3,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn diff_str_with_semantic_cleanup() {
+    let diffs = DiffOptions::new()
+        .cleanup(Cleanup::Semantic)
+        .diff_str("mouse", "sofas");
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 5,
+        },
+        Diff::Right {
+            index: 0,
+            length: 5,
+        },
+    ]);
+}
+
+#[test]
+fn diff_str_with_efficiency_cleanup() {
+    let mut expected = diff_str("abxcdyef", "abzcdwef");
+    cleanup_efficiency(&mut expected, 4);
+
+    let diffs = DiffOptions::new()
+        .cleanup(Cleanup::Efficiency { edit_cost: 4 })
+        .diff_str("abxcdyef", "abzcdwef");
+
+    assert_that!(diffs).is_equal_to(expected);
+}
+
+#[test]
+fn diff_str_with_default_options_compares_chars() {
+    let left = "lorem ipsum";
+    let right = "lorem dolor";
+
+    let diffs = DiffOptions::new().diff_str(left, right);
+
+    assert_that!(diffs).is_equal_to(diff_str(left, right));
+}
+
+#[test]
+fn diff_str_with_word_granularity_compares_words() {
+    let left = "the quick brown fox";
+    let right = "the slow brown fox";
+
+    let diffs = DiffOptions::new()
+        .granularity(Granularity::Words)
+        .diff_str(left, right);
+
+    assert_that!(diffs).is_equal_to(diff_words(left, right));
+}
+
+#[test]
+fn diff_str_with_line_granularity_compares_lines() {
+    let left = "lorem\nipsum\ndolor\n";
+    let right = "lorem\ndolor\nsit\n";
+
+    let diffs = DiffOptions::new()
+        .granularity(Granularity::Lines)
+        .diff_str(left, right);
+
+    assert_that!(diffs).is_equal_to(diff_lines(left, right));
+}
+
+#[test]
+fn options_can_be_used_for_several_diffs() {
+    let mut options = DiffOptions::new().algorithm(Algorithm::Histogram);
+
+    let first = options.diff_str("lorem", "ipsum");
+    let second = options.diff_str("lorem", "ipsum");
+
+    assert_that!(first).is_equal_to(second);
+}
+
+#[test]
+fn diff_ord_slices_with_default_options_is_the_same_as_diff_slices() {
+    let left = [1, 2, 3, 4, 5, 6];
+    let right = [1, 3, 2, 4, 6, 7];
+
+    let diffs = DiffOptions::new().diff_ord_slices(&left, &right);
+
+    assert_that!(diffs).is_equal_to(DiffOptions::new().diff_slices(&left, &right));
+}
+
+#[test]
+fn try_diff_ord_slices_of_too_long_sequences_returns_an_error() {
+    let left = vec![(); 2];
+    let right = vec![(); max_sequence_length() + 1];
+
+    let diffs = DiffOptions::new().try_diff_ord_slices(&left, &right);
+
+    assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+        side: Side::Right,
+        length: max_sequence_length() + 1,
+        max_length: max_sequence_length(),
+    });
+}
+
+#[test]
+fn try_diff_slices_of_too_long_sequences_returns_an_error() {
+    let left = vec![(); max_sequence_length() + 1];
+    let right = vec![(); 2];
+
+    let diffs = DiffOptions::new().try_diff_slices(&left, &right);
+
+    assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+        side: Side::Left,
+        length: max_sequence_length() + 1,
+        max_length: max_sequence_length(),
+    });
+}
@@ -0,0 +1,221 @@
+//! Application of a patch to a sequence that differs from the original left
+//! sequence, like `patch` does.
+
+use super::{matches_at, Change, Patch};
+use crate::std::vec::Vec;
+
+/// The number of elements a change is searched before and after its
+/// expected position by default.
+pub const DEFAULT_MAX_OFFSET: usize = 100;
+
+/// The number of context elements that may be ignored by default.
+pub const DEFAULT_FUZZ: usize = 2;
+
+/// Applies a [`Patch`] to a sequence that differs from the original left
+/// sequence.
+///
+/// Each change of the patch is located in the sequence by its removed
+/// elements and its context, like it is done by GNU `patch`:
+///
+/// 1. The change is searched at the expected position first, which is its
+///    index in the original left sequence moved by the offset of the
+///    previously applied change.
+/// 2. Then it is searched up to `max_offset` elements before and after the
+///    expected position, nearest positions first.
+/// 3. If the change is not found, the search is repeated ignoring up to
+///    `fuzz` elements of the leading and the trailing context, starting with
+///    the elements farthest away from the change. At least one element of a
+///    non-empty context is always kept, so a change is never placed without
+///    any context.
+///
+/// A change that can not be located is skipped and reported as
+/// [`ChangeOutcome::Failed`]. The other changes are applied anyway.
+///
+/// Created by [`Patch::fuzzy()`].
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{ChangeOutcome, Patch};
+///
+/// let left = [1, 2, 3, 4, 5, 6];
+/// let right = [1, 2, 3, 0, 5, 6];
+/// let patch = Patch::with_context(&left, &right, 2);
+///
+/// let drifted = [7, 7, 1, 2, 3, 4, 5, 6];
+/// let applied = patch.fuzzy().max_offset(5).apply(&drifted);
+///
+/// assert_eq!(applied.patched(), &[7, 7, 1, 2, 3, 0, 5, 6]);
+/// assert_eq!(
+///     applied.outcomes(),
+///     &[ChangeOutcome::AppliedAtOffset { offset: 2, fuzz: 0 }]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzyPatch<'a, T> {
+    patch: &'a Patch<T>,
+    max_offset: usize,
+    fuzz: usize,
+}
+
+impl<'a, T> FuzzyPatch<'a, T> {
+    pub(super) const fn new(patch: &'a Patch<T>) -> Self {
+        Self {
+            patch,
+            max_offset: DEFAULT_MAX_OFFSET,
+            fuzz: DEFAULT_FUZZ,
+        }
+    }
+
+    /// Sets the max number of elements a change is searched before and after
+    /// its expected position.
+    ///
+    /// By default, the max offset is [`DEFAULT_MAX_OFFSET`].
+    #[must_use]
+    pub const fn max_offset(mut self, max_offset: usize) -> Self {
+        self.max_offset = max_offset;
+        self
+    }
+
+    /// Sets the max number of elements of the leading and the trailing
+    /// context that may be ignored when locating a change. At least one
+    /// element of a non-empty context is kept, whatever the fuzz factor.
+    ///
+    /// By default, the fuzz factor is [`DEFAULT_FUZZ`].
+    #[must_use]
+    pub const fn fuzz(mut self, fuzz: usize) -> Self {
+        self.fuzz = fuzz;
+        self
+    }
+}
+
+impl<T> FuzzyPatch<'_, T>
+where
+    T: PartialEq + Clone,
+{
+    /// Applies the patch to the given sequence.
+    ///
+    /// Returns the patched sequence together with the outcome for each change
+    /// of the patch.
+    #[must_use]
+    pub fn apply(&self, left: &[T]) -> FuzzyApplied<T> {
+        let mut patched = Vec::with_capacity(left.len());
+        let mut outcomes = Vec::with_capacity(self.patch.changes.len());
+        let mut position = 0;
+        let mut offset = 0;
+        for change in &self.patch.changes {
+            let expected = change.left_index.saturating_add_signed(offset);
+            let Some((index, fuzz)) = self.locate(left, position, expected, change) else {
+                outcomes.push(ChangeOutcome::Failed);
+                continue;
+            };
+            #[allow(clippy::cast_possible_wrap)]
+            {
+                offset = index as isize - change.left_index as isize;
+            }
+            outcomes.push(if offset == 0 {
+                ChangeOutcome::Applied { fuzz }
+            } else {
+                ChangeOutcome::AppliedAtOffset { offset, fuzz }
+            });
+            patched.extend_from_slice(&left[position..index]);
+            patched.extend_from_slice(&change.inserted);
+            position = index + change.removed.len();
+        }
+        patched.extend_from_slice(&left[position..]);
+        FuzzyApplied { patched, outcomes }
+    }
+
+    /// Searches the position of the change in the sequence starting from the
+    /// expected position.
+    ///
+    /// Returns the found index and the number of ignored context elements.
+    fn locate(
+        &self,
+        left: &[T],
+        position: usize,
+        expected: usize,
+        change: &Change<T>,
+    ) -> Option<(usize, usize)> {
+        let leading_len = change.leading_context.len();
+        let trailing_len = change.trailing_context.len();
+        let max_fuzz = self
+            .fuzz
+            .min(leading_len.max(trailing_len).saturating_sub(1));
+        for fuzz in 0..=max_fuzz {
+            let leading = &change.leading_context[fuzz.min(leading_len.saturating_sub(1))..];
+            let trailing =
+                &change.trailing_context[..trailing_len - fuzz.min(trailing_len.saturating_sub(1))];
+            for distance in 0..=self.max_offset {
+                let candidates = [
+                    expected.checked_add(distance),
+                    expected.checked_sub(distance).filter(|_| distance > 0),
+                ];
+                for index in candidates.into_iter().flatten() {
+                    if index >= position
+                        && matches_at(left, index, &change.removed, leading, trailing)
+                    {
+                        return Some((index, fuzz));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// The result of applying a patch with [`FuzzyPatch::apply()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyApplied<T> {
+    patched: Vec<T>,
+    outcomes: Vec<ChangeOutcome>,
+}
+
+impl<T> FuzzyApplied<T> {
+    /// Returns the patched sequence.
+    #[must_use]
+    pub fn patched(&self) -> &[T] {
+        &self.patched
+    }
+
+    /// Returns the patched sequence.
+    #[must_use]
+    pub fn into_patched(self) -> Vec<T> {
+        self.patched
+    }
+
+    /// Returns the outcome for each change of the patch in order.
+    #[must_use]
+    pub fn outcomes(&self) -> &[ChangeOutcome] {
+        &self.outcomes
+    }
+
+    /// Returns whether all changes of the patch have been applied.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        !self.outcomes.contains(&ChangeOutcome::Failed)
+    }
+}
+
+/// The outcome of applying a single change of a patch with
+/// [`FuzzyPatch::apply()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChangeOutcome {
+    /// The change has been applied at its original position.
+    Applied {
+        /// The number of context elements that have been ignored on each
+        /// side of the change.
+        fuzz: usize,
+    },
+    /// The change has been applied at a different position.
+    AppliedAtOffset {
+        /// The distance from the original position of the change to the
+        /// position where it has been applied.
+        offset: isize,
+        /// The number of context elements that have been ignored on each
+        /// side of the change.
+        fuzz: usize,
+    },
+    /// The change could not be located and has not been applied.
+    Failed,
+}
@@ -0,0 +1,821 @@
+//! Application of diffs to the left sequence to reconstruct the right one.
+
+mod fuzzy;
+
+pub use fuzzy::{ChangeOutcome, FuzzyApplied, FuzzyPatch, DEFAULT_FUZZ, DEFAULT_MAX_OFFSET};
+
+use crate::std::{ops::Range, vec::Vec};
+use crate::{diff, Diff, PatchError, Side};
+
+/// Reconstructs the right sequence by applying the diffs to the left
+/// sequence.
+///
+/// Elements of [`Diff::Both`] are taken from the `left` sequence, elements of
+/// [`Diff::Right`] from the `right` sequence. See [`Patch`] for a variant
+/// that does not need the right sequence when being applied.
+///
+/// The diffs must be in ascending order and cover both sequences without
+/// gaps, as returned by [`diff()`].
+///
+/// # Errors
+///
+/// Returns an error if the diffs are not consistent with the two sequences,
+/// i.e. if a diff refers to elements out of bounds, does not start where the
+/// previous diff ended or the diffs do not cover the sequences completely.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{apply, diff};
+///
+/// let left = [1, 2, 3, 4];
+/// let right = [1, 3, 4, 5];
+/// let diffs = diff(&left, &right);
+///
+/// let patched = apply(&left, &right, &diffs);
+///
+/// assert_eq!(patched, Ok(right.to_vec()));
+/// ```
+pub fn apply<T>(left: &[T], right: &[T], diffs: &[Diff]) -> Result<Vec<T>, PatchError>
+where
+    T: Clone,
+{
+    let mut cursor = Cursor::new(left.len(), right.len());
+    let mut patched = Vec::with_capacity(right.len());
+    for diff in diffs {
+        match *diff {
+            Diff::Left { index, length } => {
+                cursor.advance(Side::Left, index, length)?;
+            },
+            Diff::Both {
+                left_index,
+                right_index,
+                length,
+            } => {
+                let range = cursor.advance(Side::Left, left_index, length)?;
+                cursor.advance(Side::Right, right_index, length)?;
+                patched.extend_from_slice(&left[range]);
+            },
+            Diff::Right { index, length } => {
+                let range = cursor.advance(Side::Right, index, length)?;
+                patched.extend_from_slice(&right[range]);
+            },
+        }
+    }
+    cursor.finish()?;
+    Ok(patched)
+}
+
+/// Verifies that the diffs are in ascending order and cover two sequences of
+/// the given lengths without gaps, as returned by [`diff()`].
+pub fn validate_diffs(left_len: usize, right_len: usize, diffs: &[Diff]) -> Result<(), PatchError> {
+    let mut cursor = Cursor::new(left_len, right_len);
+    for diff in diffs {
+        match *diff {
+            Diff::Left { index, length } => {
+                cursor.advance(Side::Left, index, length)?;
+            },
+            Diff::Both {
+                left_index,
+                right_index,
+                length,
+            } => {
+                cursor.advance(Side::Left, left_index, length)?;
+                cursor.advance(Side::Right, right_index, length)?;
+            },
+            Diff::Right { index, length } => {
+                cursor.advance(Side::Right, index, length)?;
+            },
+        }
+    }
+    cursor.finish()
+}
+
+/// A self-contained patch that transforms the left sequence into the right
+/// one and back.
+///
+/// Other than a list of [`Diff`]s, a patch owns the elements removed from and
+/// inserted into the left sequence. Only the left sequence is needed to
+/// reconstruct the right one with [`Patch::apply()`], and only the right
+/// sequence is needed to reconstruct the left one with [`Patch::revert()`].
+///
+/// With the `serde` feature enabled, patches can be serialized and
+/// deserialized, e.g. to persist them or to send them to another service.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::Patch;
+///
+/// let left = ['l', 'o', 'r', 'e', 'm'];
+/// let right = ['L', 'o', 'r', 'e', 'm', '!'];
+///
+/// let patch = Patch::new(&left, &right);
+///
+/// assert_eq!(patch.apply(&left), Ok(right.to_vec()));
+/// assert_eq!(patch.revert(&right), Ok(left.to_vec()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Patch<T> {
+    changes: Vec<Change<T>>,
+}
+
+impl<T> Patch<T>
+where
+    T: Clone,
+{
+    /// Finds the differences between the two sequences and creates a patch
+    /// from them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the slices is longer than the max supported length or
+    /// if the memory needed to find the differences can not be allocated. See
+    /// [`try_diff()`] and [`Patch::from_diffs()`] for a variant that returns an
+    /// error instead.
+    ///
+    /// [`try_diff()`]: crate::try_diff
+    #[must_use]
+    pub fn new(left: &[T], right: &[T]) -> Self
+    where
+        T: PartialEq,
+    {
+        let diffs = diff(left, right);
+        Self::from_diffs(left, right, &diffs).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Finds the differences between the two sequences and creates a patch
+    /// from them that contains up to `context` unchanged elements before and
+    /// after each change.
+    ///
+    /// The context is needed to locate the changes in a sequence that
+    /// differs from the original left sequence, see [`Patch::fuzzy()`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the slices is longer than the max supported length or
+    /// if the memory needed to find the differences can not be allocated. See
+    /// [`try_diff()`] and [`Patch::from_diffs_with_context()`] for a variant
+    /// that returns an error instead.
+    ///
+    /// [`try_diff()`]: crate::try_diff
+    #[must_use]
+    pub fn with_context(left: &[T], right: &[T], context: usize) -> Self
+    where
+        T: PartialEq,
+    {
+        let diffs = diff(left, right);
+        Self::from_diffs_with_context(left, right, &diffs, context)
+            .unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    /// Creates a patch from the diffs between the two sequences.
+    ///
+    /// The diffs must be in ascending order and cover both sequences without
+    /// gaps, as returned by [`diff()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the diffs are not consistent with the two
+    /// sequences, i.e. if a diff refers to elements out of bounds, does not
+    /// start where the previous diff ended or the diffs do not cover the
+    /// sequences completely.
+    pub fn from_diffs(left: &[T], right: &[T], diffs: &[Diff]) -> Result<Self, PatchError> {
+        Self::from_diffs_with_context(left, right, diffs, 0)
+    }
+
+    /// Creates a patch from the diffs between the two sequences that contains
+    /// up to `context` unchanged elements before and after each change.
+    ///
+    /// The diffs must be in ascending order and cover both sequences without
+    /// gaps, as returned by [`diff()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the diffs are not consistent with the two
+    /// sequences, i.e. if a diff refers to elements out of bounds, does not
+    /// start where the previous diff ended or the diffs do not cover the
+    /// sequences completely.
+    pub fn from_diffs_with_context(
+        left: &[T],
+        right: &[T],
+        diffs: &[Diff],
+        context: usize,
+    ) -> Result<Self, PatchError> {
+        let mut cursor = Cursor::new(left.len(), right.len());
+        let mut changes: Vec<Change<T>> = Vec::new();
+        for diff in diffs {
+            let (left_range, right_range) = match *diff {
+                Diff::Left { index, length } => {
+                    let right_index = cursor.position(Side::Right);
+                    let left_range = cursor.advance(Side::Left, index, length)?;
+                    (left_range, right_index..right_index)
+                },
+                Diff::Both {
+                    left_index,
+                    right_index,
+                    length,
+                } => {
+                    cursor.advance(Side::Left, left_index, length)?;
+                    cursor.advance(Side::Right, right_index, length)?;
+                    continue;
+                },
+                Diff::Right { index, length } => {
+                    let left_index = cursor.position(Side::Left);
+                    let right_range = cursor.advance(Side::Right, index, length)?;
+                    (left_index..left_index, right_range)
+                },
+            };
+            if left_range.is_empty() && right_range.is_empty() {
+                continue;
+            }
+            match changes.last_mut() {
+                Some(change)
+                    if change.left_range().end == left_range.start
+                        && change.right_range().end == right_range.start =>
+                {
+                    change.removed.extend_from_slice(&left[left_range]);
+                    change.inserted.extend_from_slice(&right[right_range]);
+                },
+                _ => changes.push(Change {
+                    left_index: left_range.start,
+                    right_index: right_range.start,
+                    removed: left[left_range].to_vec(),
+                    inserted: right[right_range].to_vec(),
+                    leading_context: Vec::new(),
+                    trailing_context: Vec::new(),
+                }),
+            }
+        }
+        cursor.finish()?;
+
+        if context > 0 {
+            let ends: Vec<_> = changes
+                .iter()
+                .map(|change| change.left_range().end)
+                .collect();
+            let starts: Vec<_> = changes.iter().map(|change| change.left_index).collect();
+            for (number, change) in changes.iter_mut().enumerate() {
+                let previous_end = number.checked_sub(1).map_or(0, |previous| ends[previous]);
+                let next_start = starts.get(number + 1).copied().unwrap_or(left.len());
+                let start = change.left_index.saturating_sub(context).max(previous_end);
+                let end = ends[number].saturating_add(context).min(next_start);
+                change.leading_context = left[start..change.left_index].to_vec();
+                change.trailing_context = left[ends[number]..end].to_vec();
+            }
+        }
+        Ok(Self { changes })
+    }
+
+    /// Applies this patch to the left sequence and returns the reconstructed
+    /// right sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given sequence does not contain the elements
+    /// removed by this patch and their context at the expected positions or
+    /// if the changes of this patch are not consistent with each other. See
+    /// [`Patch::fuzzy()`] for applying a patch to a sequence that differs
+    /// from the original left sequence.
+    pub fn apply(&self, left: &[T]) -> Result<Vec<T>, PatchError>
+    where
+        T: PartialEq,
+    {
+        self.patch(left, Side::Left)
+    }
+
+    /// Reverts this patch on the right sequence and returns the reconstructed
+    /// left sequence.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the given sequence does not contain the elements
+    /// inserted by this patch and their context at the expected positions or
+    /// if the changes of this patch are not consistent with each other.
+    pub fn revert(&self, right: &[T]) -> Result<Vec<T>, PatchError>
+    where
+        T: PartialEq,
+    {
+        self.patch(right, Side::Right)
+    }
+
+    /// Returns the inverted patch, which transforms the right sequence into
+    /// the left one.
+    ///
+    /// Applying the inverted patch is the same as reverting this patch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sdiff::Patch;
+    ///
+    /// let left = [1, 2, 3];
+    /// let right = [1, 3, 4];
+    ///
+    /// let patch = Patch::new(&left, &right);
+    /// let inverted = patch.invert();
+    ///
+    /// assert_eq!(inverted.apply(&right), Ok(left.to_vec()));
+    /// assert_eq!(inverted, Patch::new(&right, &left));
+    /// ```
+    #[must_use]
+    pub fn invert(&self) -> Self {
+        let changes = self
+            .changes
+            .iter()
+            .map(|change| Change {
+                left_index: change.right_index,
+                right_index: change.left_index,
+                removed: change.inserted.clone(),
+                inserted: change.removed.clone(),
+                leading_context: change.leading_context.clone(),
+                trailing_context: change.trailing_context.clone(),
+            })
+            .collect();
+        Self { changes }
+    }
+
+    /// Composes this patch with the given patch, which is applied after this
+    /// one.
+    ///
+    /// If this patch transforms the sequence *A* into *B* and `other`
+    /// transforms *B* into *C*, the composed patch transforms *A* into *C*.
+    /// Overlapping changes of the two patches are merged into one change.
+    /// Changes that cancel each other out are dropped. The composed patch
+    /// is not necessarily the shortest one that transforms *A* into *C*, and
+    /// it does not contain any context.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the elements `other` removes from *B* do not match
+    /// the elements this patch inserts into *B*.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sdiff::Patch;
+    ///
+    /// let a = ['l', 'o', 'r', 'e', 'm'];
+    /// let b = ['L', 'o', 'r', 'e', 'm'];
+    /// let c = ['L', 'o', 'r', 'e'];
+    ///
+    /// let a_to_b = Patch::new(&a, &b);
+    /// let b_to_c = Patch::new(&b, &c);
+    /// let a_to_c = a_to_b.compose(&b_to_c);
+    ///
+    /// assert_eq!(a_to_c.and_then(|patch| patch.apply(&a)), Ok(c.to_vec()));
+    /// ```
+    pub fn compose(&self, other: &Self) -> Result<Self, PatchError>
+    where
+        T: PartialEq,
+    {
+        let mut changes = Vec::new();
+        let mut firsts = self.changes.iter().peekable();
+        let mut seconds = other.changes.iter().peekable();
+        let mut last_first: Option<&Change<T>> = None;
+        let mut last_second: Option<&Change<T>> = None;
+
+        // Groups of changes are formed by ranges in the intermediate sequence,
+        // which are the right ranges of this patch and the left ranges of the
+        // other patch. Overlapping or touching ranges belong to the same group.
+        loop {
+            let start = match (firsts.peek(), seconds.peek()) {
+                (Some(first), Some(second)) => first.right_index.min(second.left_index),
+                (Some(first), None) => first.right_index,
+                (None, Some(second)) => second.left_index,
+                (None, None) => break,
+            };
+            let mut end = start;
+            let mut group_firsts = Vec::new();
+            let mut group_seconds = Vec::new();
+            loop {
+                if let Some(first) = firsts.next_if(|first| first.right_index <= end) {
+                    end = end.max(first.right_range().end);
+                    group_firsts.push(first);
+                } else if let Some(second) = seconds.next_if(|second| second.left_index <= end) {
+                    end = end.max(second.left_range().end);
+                    group_seconds.push(second);
+                } else {
+                    break;
+                }
+            }
+
+            let left_index = match group_firsts.first() {
+                Some(first) if first.right_index == start => first.left_index,
+                _ => last_first.map_or(start, |first| {
+                    start - first.right_range().end + first.left_range().end
+                }),
+            };
+            let right_index = match group_seconds.first() {
+                Some(second) if second.left_index == start => second.right_index,
+                _ => last_second.map_or(start, |second| {
+                    start - second.left_range().end + second.right_range().end
+                }),
+            };
+            last_first = group_firsts.last().copied().or(last_first);
+            last_second = group_seconds.last().copied().or(last_second);
+
+            let inserted_by_firsts: Vec<_> = group_firsts
+                .iter()
+                .map(|first| (first.right_range(), &first.inserted[..]))
+                .collect();
+            let removed_by_seconds: Vec<_> = group_seconds
+                .iter()
+                .map(|second| (second.left_range(), &second.removed[..]))
+                .collect();
+            verify_overlaps(&inserted_by_firsts, &removed_by_seconds)?;
+
+            let removed_by_firsts: Vec<_> = group_firsts
+                .iter()
+                .map(|first| (first.right_range(), &first.removed[..]))
+                .collect();
+            let inserted_by_seconds: Vec<_> = group_seconds
+                .iter()
+                .map(|second| (second.left_range(), &second.inserted[..]))
+                .collect();
+            let removed = compose_elements(start, end, &removed_by_firsts, &removed_by_seconds);
+            let inserted = compose_elements(start, end, &inserted_by_seconds, &inserted_by_firsts);
+            if removed != inserted {
+                changes.push(Change {
+                    left_index,
+                    right_index,
+                    removed,
+                    inserted,
+                    leading_context: Vec::new(),
+                    trailing_context: Vec::new(),
+                });
+            }
+        }
+        Ok(Self { changes })
+    }
+
+    /// Replaces the elements of each change on the given side of the patch
+    /// with the elements on the other side.
+    fn patch(&self, source: &[T], side: Side) -> Result<Vec<T>, PatchError>
+    where
+        T: PartialEq,
+    {
+        let target_side = match side {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        };
+        let mut patched = Vec::with_capacity(source.len());
+        let mut position = 0;
+        for change in &self.changes {
+            let (index, old, target_index, new) = match side {
+                Side::Left => (
+                    change.left_index,
+                    &change.removed,
+                    change.right_index,
+                    &change.inserted,
+                ),
+                Side::Right => (
+                    change.right_index,
+                    &change.inserted,
+                    change.left_index,
+                    &change.removed,
+                ),
+            };
+            if index < position {
+                return Err(PatchError::UnexpectedIndex {
+                    side,
+                    index,
+                    expected: position,
+                });
+            }
+            let end = index
+                .checked_add(old.len())
+                .filter(|end| *end <= source.len())
+                .ok_or(PatchError::OutOfBounds {
+                    side,
+                    index,
+                    length: old.len(),
+                    sequence_length: source.len(),
+                })?;
+            if !matches_at(
+                source,
+                index,
+                old,
+                &change.leading_context,
+                &change.trailing_context,
+            ) {
+                return Err(PatchError::Mismatch { side, index });
+            }
+            patched.extend_from_slice(&source[position..index]);
+            if target_index != patched.len() {
+                return Err(PatchError::UnexpectedIndex {
+                    side: target_side,
+                    index: target_index,
+                    expected: patched.len(),
+                });
+            }
+            patched.extend_from_slice(new);
+            position = end;
+        }
+        patched.extend_from_slice(&source[position..]);
+        Ok(patched)
+    }
+}
+
+impl<T> Patch<T> {
+    pub(crate) const fn from_changes(changes: Vec<Change<T>>) -> Self {
+        Self { changes }
+    }
+
+    /// Returns a [`FuzzyPatch`] for applying this patch to a sequence that
+    /// differs from the original left sequence.
+    ///
+    /// Fuzzy application works best for patches created with context, like
+    /// by [`Patch::with_context()`].
+    #[must_use]
+    pub const fn fuzzy(&self) -> FuzzyPatch<'_, T> {
+        FuzzyPatch::new(self)
+    }
+
+    /// Returns the changes of this patch in ascending order.
+    #[must_use]
+    pub fn changes(&self) -> &[Change<T>] {
+        &self.changes
+    }
+
+    /// Returns whether this patch does not change anything.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A single change of a [`Patch`].
+///
+/// A change removes elements from the left sequence and inserts other
+/// elements in their place. It owns both, the removed and the inserted
+/// elements, as well as the unchanged elements before and after the change,
+/// if the patch has been created with context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Change<T> {
+    left_index: usize,
+    right_index: usize,
+    removed: Vec<T>,
+    inserted: Vec<T>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")
+    )]
+    leading_context: Vec<T>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")
+    )]
+    trailing_context: Vec<T>,
+}
+
+impl<T> Change<T> {
+    pub(crate) const fn new(
+        left_index: usize,
+        right_index: usize,
+        removed: Vec<T>,
+        inserted: Vec<T>,
+        leading_context: Vec<T>,
+        trailing_context: Vec<T>,
+    ) -> Self {
+        Self {
+            left_index,
+            right_index,
+            removed,
+            inserted,
+            leading_context,
+            trailing_context,
+        }
+    }
+
+    /// The index in the left sequence where the elements are removed.
+    #[must_use]
+    pub const fn left_index(&self) -> usize {
+        self.left_index
+    }
+
+    /// The index in the right sequence where the elements are inserted.
+    #[must_use]
+    pub const fn right_index(&self) -> usize {
+        self.right_index
+    }
+
+    /// The range of the removed elements in the left sequence.
+    ///
+    /// The range is empty if this change only inserts elements. The end of
+    /// the range saturates at `usize::MAX`.
+    #[must_use]
+    pub fn left_range(&self) -> Range<usize> {
+        self.left_index..self.left_index.saturating_add(self.removed.len())
+    }
+
+    /// The range of the inserted elements in the right sequence.
+    ///
+    /// The range is empty if this change only removes elements. The end of
+    /// the range saturates at `usize::MAX`.
+    #[must_use]
+    pub fn right_range(&self) -> Range<usize> {
+        self.right_index..self.right_index.saturating_add(self.inserted.len())
+    }
+
+    /// The elements removed from the left sequence.
+    #[must_use]
+    pub fn removed(&self) -> &[T] {
+        &self.removed
+    }
+
+    /// The elements inserted in place of the removed ones.
+    #[must_use]
+    pub fn inserted(&self) -> &[T] {
+        &self.inserted
+    }
+
+    /// The unchanged elements right before this change.
+    #[must_use]
+    pub fn leading_context(&self) -> &[T] {
+        &self.leading_context
+    }
+
+    /// The unchanged elements right after this change.
+    #[must_use]
+    pub fn trailing_context(&self) -> &[T] {
+        &self.trailing_context
+    }
+}
+
+/// Returns whether the `source` contains the elements `old` at `index`,
+/// preceded by the `leading` context and followed by the `trailing` context.
+fn matches_at<T>(source: &[T], index: usize, old: &[T], leading: &[T], trailing: &[T]) -> bool
+where
+    T: PartialEq,
+{
+    let (Some(start), Some(end)) = (
+        index.checked_sub(leading.len()),
+        index.checked_add(old.len()),
+    ) else {
+        return false;
+    };
+    let Some(trailing_end) = end
+        .checked_add(trailing.len())
+        .filter(|trailing_end| *trailing_end <= source.len())
+    else {
+        return false;
+    };
+    source[start..index] == *leading
+        && source[index..end] == *old
+        && source[end..trailing_end] == *trailing
+}
+
+/// Elements of the intermediate sequence of two composed patches together
+/// with their range in the intermediate sequence.
+type Segment<'a, T> = (Range<usize>, &'a [T]);
+
+/// Verifies that the elements inserted by the first patch match the elements
+/// removed by the second patch where they overlap.
+fn verify_overlaps<T>(
+    inserted: &[Segment<'_, T>],
+    removed: &[Segment<'_, T>],
+) -> Result<(), PatchError>
+where
+    T: PartialEq,
+{
+    for (inserted_range, inserted) in inserted {
+        for (removed_range, removed) in removed {
+            let start = inserted_range.start.max(removed_range.start);
+            let end = inserted_range.end.min(removed_range.end);
+            if start < end
+                && inserted[start - inserted_range.start..end - inserted_range.start]
+                    != removed[start - removed_range.start..end - removed_range.start]
+            {
+                return Err(PatchError::Mismatch {
+                    side: Side::Left,
+                    index: start,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collects the elements that replace the range `start..end` of the
+/// intermediate sequence of two composed patches.
+///
+/// Where one of the `replacing` segments starts, its elements are taken and
+/// the range it replaces is skipped. Everywhere else the elements of the
+/// intermediate sequence are taken from the `covering` segments.
+fn compose_elements<T>(
+    start: usize,
+    end: usize,
+    replacing: &[Segment<'_, T>],
+    covering: &[Segment<'_, T>],
+) -> Vec<T>
+where
+    T: Clone,
+{
+    let mut elements = Vec::new();
+    let mut position = start;
+    let mut replacing = replacing.iter().peekable();
+    loop {
+        if let Some((range, replacement)) = replacing.next_if(|(range, _)| range.start <= position)
+        {
+            elements.extend_from_slice(replacement);
+            position = position.max(range.end);
+        } else if position < end {
+            let next = replacing.peek().map_or(end, |(range, _)| range.start);
+            let Some((range, covered)) =
+                covering.iter().find(|(range, _)| range.contains(&position))
+            else {
+                unreachable!("the range of a group is covered by the changes of the group")
+            };
+            let until = range.end.min(next);
+            elements.extend_from_slice(&covered[position - range.start..until - range.start]);
+            position = until;
+        } else {
+            break;
+        }
+    }
+    elements
+}
+
+/// Keeps track of the positions in both sequences while iterating over
+/// diffs and validates that the diffs are consistent with the sequences.
+struct Cursor {
+    left_len: usize,
+    right_len: usize,
+    left_pos: usize,
+    right_pos: usize,
+}
+
+impl Cursor {
+    const fn new(left_len: usize, right_len: usize) -> Self {
+        Self {
+            left_len,
+            right_len,
+            left_pos: 0,
+            right_pos: 0,
+        }
+    }
+
+    const fn position(&self, side: Side) -> usize {
+        match side {
+            Side::Left => self.left_pos,
+            Side::Right => self.right_pos,
+        }
+    }
+
+    /// Advances the position in the given sequence by `length` elements
+    /// starting at `index` and returns the range of the passed elements.
+    fn advance(
+        &mut self,
+        side: Side,
+        index: usize,
+        length: usize,
+    ) -> Result<Range<usize>, PatchError> {
+        let (position, sequence_length) = match side {
+            Side::Left => (&mut self.left_pos, self.left_len),
+            Side::Right => (&mut self.right_pos, self.right_len),
+        };
+        if index != *position {
+            return Err(PatchError::UnexpectedIndex {
+                side,
+                index,
+                expected: *position,
+            });
+        }
+        let end = index
+            .checked_add(length)
+            .filter(|end| *end <= sequence_length)
+            .ok_or(PatchError::OutOfBounds {
+                side,
+                index,
+                length,
+                sequence_length,
+            })?;
+        *position = end;
+        Ok(index..end)
+    }
+
+    /// Verifies that both sequences have been covered completely.
+    const fn finish(&self) -> Result<(), PatchError> {
+        if self.left_pos < self.left_len {
+            return Err(PatchError::Incomplete {
+                side: Side::Left,
+                covered_length: self.left_pos,
+                sequence_length: self.left_len,
+            });
+        }
+        if self.right_pos < self.right_len {
+            return Err(PatchError::Incomplete {
+                side: Side::Right,
+                covered_length: self.right_pos,
+                sequence_length: self.right_len,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
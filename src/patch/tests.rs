@@ -0,0 +1,715 @@
+use super::*;
+use crate::std::vec;
+use asserting::prelude::*;
+
+fn change<T>(
+    left_index: usize,
+    right_index: usize,
+    removed: Vec<T>,
+    inserted: Vec<T>,
+) -> Change<T> {
+    Change {
+        left_index,
+        right_index,
+        removed,
+        inserted,
+        leading_context: vec![],
+        trailing_context: vec![],
+    }
+}
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn applying_the_diffs_to_left_reconstructs_right(
+            left in prop::collection::vec(0..8_u8, 0..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+        ) {
+            let diffs = diff(&left, &right);
+
+            prop_assert_eq!(apply(&left, &right, &diffs), Ok(right));
+        }
+
+        #[test]
+        fn applying_a_patch_to_left_reconstructs_right(
+            left in prop::collection::vec(0..8_u8, 0..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+        ) {
+            let patch = Patch::new(&left, &right);
+
+            prop_assert_eq!(patch.apply(&left), Ok(right));
+        }
+
+        #[test]
+        fn reverting_a_patch_on_right_reconstructs_left(
+            left in prop::collection::vec(0..8_u8, 0..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+        ) {
+            let patch = Patch::new(&left, &right);
+
+            prop_assert_eq!(patch.revert(&right), Ok(left));
+        }
+
+        #[test]
+        fn applying_an_inverted_patch_to_right_reconstructs_left(
+            left in prop::collection::vec(0..8_u8, 0..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+        ) {
+            let patch = Patch::new(&left, &right).invert();
+
+            prop_assert_eq!(patch.apply(&right), Ok(left));
+        }
+
+        #[test]
+        fn a_composed_patch_transforms_the_first_sequence_into_the_last(
+            a in prop::collection::vec(0..6_u8, 0..=40),
+            b in prop::collection::vec(0..6_u8, 0..=40),
+            c in prop::collection::vec(0..6_u8, 0..=40),
+        ) {
+            let a_to_b = Patch::new(&a, &b);
+            let b_to_c = Patch::new(&b, &c);
+
+            let a_to_c = a_to_b.compose(&b_to_c).unwrap_or_else(|err| panic!("{err}"));
+
+            prop_assert_eq!(a_to_c.apply(&a), Ok(c.clone()));
+            prop_assert_eq!(a_to_c.revert(&c), Ok(a));
+        }
+
+        #[test]
+        fn applying_a_patch_with_context_fuzzily_to_left_reconstructs_right(
+            left in prop::collection::vec(0..8_u8, 0..=60),
+            right in prop::collection::vec(0..8_u8, 0..=60),
+            context in 0..4_usize,
+        ) {
+            let patch = Patch::with_context(&left, &right, context);
+
+            let applied = patch.fuzzy().apply(&left);
+
+            prop_assert_eq!(patch.apply(&left), Ok(right.clone()));
+            prop_assert_eq!(applied.patched(), &right[..]);
+            let exact = ChangeOutcome::Applied { fuzz: 0 };
+            prop_assert!(applied.outcomes().iter().all(|outcome| *outcome == exact));
+        }
+    }
+}
+
+#[test]
+fn apply_diffs_of_two_empty_sequences() {
+    let left: [char; 0] = [];
+    let right: [char; 0] = [];
+    let diffs = diff(&left, &right);
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_value(vec![]);
+}
+
+#[test]
+fn apply_diffs_to_left_sequence() {
+    let left = ['a', 'b', 'c', 'a', 'b', 'b', 'a'];
+    let right = ['c', 'b', 'a', 'b', 'a', 'c'];
+    let diffs = diff(&left, &right);
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_value(right.to_vec());
+}
+
+#[test]
+fn apply_diffs_with_left_index_out_of_bounds() {
+    let left = [1, 2, 3];
+    let right = [1, 2, 3, 4];
+    let diffs = [
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 3,
+        },
+        Diff::Left {
+            index: 3,
+            length: 1,
+        },
+    ];
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_error(PatchError::OutOfBounds {
+        side: Side::Left,
+        index: 3,
+        length: 1,
+        sequence_length: 3,
+    });
+}
+
+#[test]
+fn apply_diffs_with_both_length_out_of_bounds_of_right() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 2];
+    let diffs = [Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 4,
+    }];
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_error(PatchError::OutOfBounds {
+        side: Side::Right,
+        index: 0,
+        length: 4,
+        sequence_length: 2,
+    });
+}
+
+#[test]
+fn apply_diffs_with_a_gap() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 4];
+    let diffs = [
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 1,
+            length: 1,
+        },
+    ];
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_error(PatchError::UnexpectedIndex {
+        side: Side::Left,
+        index: 2,
+        expected: 1,
+    });
+}
+
+#[test]
+fn apply_diffs_not_covering_the_left_sequence() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 2];
+    let diffs = [Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 2,
+    }];
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_error(PatchError::Incomplete {
+        side: Side::Left,
+        covered_length: 2,
+        sequence_length: 4,
+    });
+}
+
+#[test]
+fn error_message_of_out_of_bounds() {
+    let left = [1, 2];
+    let right = [1, 2, 3];
+    let diffs = [Diff::Right {
+        index: 0,
+        length: 5,
+    }];
+
+    let patched = apply(&left, &right, &diffs);
+
+    assert_that!(patched).has_error_message(
+        "the range 0..5 is out of bounds of the right sequence, its length is 3",
+    );
+}
+
+#[test]
+fn patch_owns_the_removed_and_inserted_elements() {
+    let left = ['l', 'o', 'r', 'e', 'm'];
+    let right = ['L', 'o', 'r', 'e', 'm', '!'];
+
+    let patch = Patch::new(&left, &right);
+
+    assert_that!(patch.changes()).is_equal_to(
+        &[
+            change(0, 0, vec!['l'], vec!['L']),
+            change(5, 5, vec![], vec!['!']),
+        ][..],
+    );
+}
+
+#[test]
+fn patch_of_equal_sequences_is_empty() {
+    let left = [1, 2, 3];
+
+    let patch = Patch::new(&left, &left);
+
+    assert_that!(patch.is_empty()).is_true();
+    assert_that!(patch.apply(&left)).has_value(left.to_vec());
+}
+
+#[test]
+fn patch_from_inconsistent_diffs() {
+    let left = [1, 2, 3];
+    let right = [1, 2];
+    let diffs = [
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Right {
+            index: 1,
+            length: 1,
+        },
+    ];
+
+    let patch = Patch::from_diffs(&left, &right, &diffs);
+
+    assert_that!(patch).has_error(PatchError::UnexpectedIndex {
+        side: Side::Right,
+        index: 1,
+        expected: 2,
+    });
+}
+
+#[test]
+fn apply_patch_to_a_too_short_sequence() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 2, 3];
+    let patch = Patch::new(&left, &right);
+
+    let patched = patch.apply(&left[..2]);
+
+    assert_that!(patched).has_error(PatchError::OutOfBounds {
+        side: Side::Left,
+        index: 3,
+        length: 1,
+        sequence_length: 2,
+    });
+}
+
+#[test]
+fn revert_patch_on_right_sequence() {
+    let left = ['a', 'b', 'c', 'a', 'b', 'b', 'a'];
+    let right = ['c', 'b', 'a', 'b', 'a', 'c'];
+    let patch = Patch::new(&left, &right);
+
+    let reverted = patch.revert(&right);
+
+    assert_that!(reverted).has_value(left.to_vec());
+}
+
+#[test]
+fn apply_patch_to_a_sequence_not_containing_the_removed_elements() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 3, 4];
+    let patch = Patch::new(&left, &right);
+
+    let patched = patch.apply(&[1, 5, 3, 4]);
+
+    assert_that!(patched).has_error(PatchError::Mismatch {
+        side: Side::Left,
+        index: 1,
+    });
+}
+
+#[test]
+fn revert_patch_on_a_sequence_not_containing_the_inserted_elements() {
+    let left = [1, 2, 3];
+    let right = [1, 2, 3, 4];
+    let patch = Patch::new(&left, &right);
+
+    let reverted = patch.revert(&[1, 2, 3, 5]);
+
+    assert_that!(reverted).has_error(PatchError::Mismatch {
+        side: Side::Right,
+        index: 3,
+    });
+}
+
+#[test]
+fn apply_patch_with_inconsistent_right_index() {
+    let patch = Patch {
+        changes: vec![change(1, 2, vec![2], vec![7])],
+    };
+
+    let patched = patch.apply(&[1, 2, 3]);
+
+    assert_that!(patched).has_error(PatchError::UnexpectedIndex {
+        side: Side::Right,
+        index: 2,
+        expected: 1,
+    });
+}
+
+#[test]
+fn error_message_of_mismatch() {
+    let patch = Patch::new(&['a', 'b'], &['a', 'c']);
+
+    let patched = patch.apply(&['a', 'x']);
+
+    assert_that!(patched)
+        .has_error_message("the elements at index 1 of the left sequence do not match the patch");
+}
+
+#[test]
+fn apply_patch_with_an_index_near_the_max_value_returns_an_error() {
+    let patch = Patch::from_changes(vec![change(usize::MAX, 0, vec![1, 2], vec![3])]);
+
+    let patched = patch.apply(&[1, 2, 3]);
+
+    assert_that!(patched).has_error(PatchError::OutOfBounds {
+        side: Side::Left,
+        index: usize::MAX,
+        length: 2,
+        sequence_length: 3,
+    });
+}
+
+#[test]
+fn revert_patch_with_an_index_near_the_max_value_returns_an_error() {
+    let patch = Patch::from_changes(vec![change(0, usize::MAX - 1, vec![1], vec![3, 4])]);
+
+    let reverted = patch.revert(&[3, 4]);
+
+    assert_that!(reverted).has_error(PatchError::OutOfBounds {
+        side: Side::Right,
+        index: usize::MAX - 1,
+        length: 2,
+        sequence_length: 2,
+    });
+}
+
+#[test]
+fn ranges_of_a_change_with_an_index_near_the_max_value_saturate() {
+    let change = change(usize::MAX - 1, usize::MAX, vec![1, 2, 3], vec![4]);
+
+    assert_that!(change.left_range()).is_equal_to(usize::MAX - 1..usize::MAX);
+    assert_that!(change.right_range()).is_equal_to(usize::MAX..usize::MAX);
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use super::*;
+    use crate::std::string::String;
+
+    #[test]
+    fn serialize_and_deserialize_patch() {
+        let left = [String::from("lorem"), String::from("ipsum")];
+        let right = [String::from("lorem"), String::from("dolor")];
+        let patch = Patch::new(&left, &right);
+
+        let json = serde_json::to_string(&patch).unwrap_or_else(|err| panic!("{err}"));
+        let deserialized: Patch<String> =
+            serde_json::from_str(&json).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(json).is_equal_to(
+            r#"{"changes":[{"left_index":1,"right_index":1,"removed":["ipsum"],"inserted":["dolor"]}]}"#,
+        );
+        assert_that!(deserialized.apply(&left)).has_value(right.to_vec());
+    }
+
+    #[test]
+    fn serialize_and_deserialize_patch_with_context() {
+        let left = [1, 2, 3, 4, 5];
+        let right = [1, 2, 0, 4, 5];
+        let patch = Patch::with_context(&left, &right, 1);
+
+        let json = serde_json::to_string(&patch).unwrap_or_else(|err| panic!("{err}"));
+        let deserialized: Patch<u8> =
+            serde_json::from_str(&json).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(json).is_equal_to(
+            r#"{"changes":[{"left_index":2,"right_index":2,"removed":[3],"inserted":[0],"leading_context":[2],"trailing_context":[4]}]}"#,
+        );
+        assert_that!(deserialized).is_equal_to(patch);
+    }
+
+    #[test]
+    fn deserialize_patch_serialized_without_context_fields() {
+        let left = [1, 2, 3];
+        let right = [1, 0, 3];
+        let json = r#"{"changes":[{"left_index":1,"right_index":1,"removed":[2],"inserted":[0]}]}"#;
+
+        let deserialized: Patch<u8> =
+            serde_json::from_str(json).unwrap_or_else(|err| panic!("{err}"));
+
+        let serialized = serde_json::to_string(&deserialized).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(serialized).is_equal_to(json);
+        assert_that!(deserialized).is_equal_to(Patch::new(&left, &right));
+    }
+
+    #[test]
+    fn apply_deserialized_patch_with_an_index_near_the_max_value_returns_an_error() {
+        let json = r#"{"changes":[{"left_index":18446744073709551615,"right_index":0,"removed":[1],"inserted":[2]}]}"#;
+        let patch: Patch<u8> = serde_json::from_str(json).unwrap_or_else(|err| panic!("{err}"));
+
+        let patched = patch.apply(&[1, 2, 3]);
+        let applied = patch.fuzzy().apply(&[1, 2, 3]);
+
+        assert_that!(patched).has_error(PatchError::OutOfBounds {
+            side: Side::Left,
+            index: 18_446_744_073_709_551_615,
+            length: 1,
+            sequence_length: 3,
+        });
+        assert_that!(applied.outcomes()).is_equal_to(&[ChangeOutcome::Failed][..]);
+    }
+}
+
+#[test]
+fn inverted_patch_swaps_removed_and_inserted_elements() {
+    let patch = Patch::new(&[1, 2, 3], &[1, 4, 3, 5]);
+
+    let inverted = patch.invert();
+
+    assert_that!(inverted.changes()).is_equal_to(
+        &[
+            change(1, 1, vec![4], vec![2]),
+            change(3, 3, vec![5], vec![]),
+        ][..],
+    );
+}
+
+#[test]
+fn compose_patches_with_overlapping_changes() {
+    let a = ['a', 'b', 'c', 'd', 'e'];
+    let b = ['a', 'x', 'y', 'd', 'e'];
+    let c = ['a', 'x', 'z', 'e'];
+    let a_to_b = Patch::new(&a, &b);
+    let b_to_c = Patch::new(&b, &c);
+
+    let a_to_c = a_to_b.compose(&b_to_c);
+
+    assert_that!(a_to_c).has_value(Patch {
+        changes: vec![change(1, 1, vec!['b', 'c', 'd'], vec!['x', 'z'])],
+    });
+}
+
+#[test]
+fn compose_patches_with_separate_changes() {
+    let a = [1, 2, 3, 4, 5, 6];
+    let b = [0, 1, 2, 3, 4, 5, 6];
+    let c = [0, 1, 2, 3, 4, 6];
+    let a_to_b = Patch::new(&a, &b);
+    let b_to_c = Patch::new(&b, &c);
+
+    let a_to_c = a_to_b.compose(&b_to_c);
+
+    assert_that!(a_to_c).has_value(Patch {
+        changes: vec![change(0, 0, vec![], vec![0]), change(4, 5, vec![5], vec![])],
+    });
+}
+
+#[test]
+fn changes_cancelling_each_other_out_are_dropped_when_composing() {
+    let a = ['l', 'o', 'r', 'e', 'm'];
+    let b = ['l', 'o', 'r', 'e', 'm', '!'];
+    let a_to_b = Patch::new(&a, &b);
+
+    let a_to_a = a_to_b.compose(&a_to_b.invert());
+
+    assert_that!(a_to_a.map(|patch| patch.is_empty())).has_value(true);
+}
+
+#[test]
+fn compose_patches_that_do_not_fit_together() {
+    let a_to_b = Patch::new(&[1, 2, 3], &[1, 4, 3]);
+    let x_to_y = Patch::new(&[1, 5, 3], &[1, 3]);
+
+    let composed = a_to_b.compose(&x_to_y);
+
+    assert_that!(composed).has_error(PatchError::Mismatch {
+        side: Side::Left,
+        index: 1,
+    });
+}
+
+#[test]
+fn patch_with_context_contains_unchanged_elements_around_changes() {
+    let left = [1, 2, 3, 4, 5, 6, 7, 8];
+    let right = [1, 2, 0, 4, 5, 6, 8];
+
+    let patch = Patch::with_context(&left, &right, 2);
+
+    assert_that!(patch.changes().len()).is_equal_to(2);
+    let first = &patch.changes()[0];
+    assert_that!(first.leading_context()).is_equal_to(&[1, 2][..]);
+    assert_that!(first.trailing_context()).is_equal_to(&[4, 5][..]);
+    let second = &patch.changes()[1];
+    assert_that!(second.leading_context()).is_equal_to(&[5, 6][..]);
+    assert_that!(second.trailing_context()).is_equal_to(&[8][..]);
+}
+
+#[test]
+fn context_of_a_change_does_not_reach_into_neighboring_changes() {
+    let left = [1, 2, 3, 4, 5];
+    let right = [0, 2, 0, 4, 0];
+
+    let patch = Patch::with_context(&left, &right, 3);
+
+    let contexts: Vec<_> = patch
+        .changes()
+        .iter()
+        .map(|change| (change.leading_context(), change.trailing_context()))
+        .collect();
+    assert_that!(contexts).is_equal_to(vec![
+        (&[][..], &[2][..]),
+        (&[2][..], &[4][..]),
+        (&[4][..], &[][..]),
+    ]);
+}
+
+#[test]
+fn apply_patch_to_a_sequence_not_containing_the_context() {
+    let left = [1, 2, 3, 4];
+    let right = [1, 2, 0, 4];
+    let patch = Patch::with_context(&left, &right, 1);
+
+    let patched = patch.apply(&[1, 5, 3, 4]);
+
+    assert_that!(patched).has_error(PatchError::Mismatch {
+        side: Side::Left,
+        index: 2,
+    });
+}
+
+mod fuzzy {
+    use super::*;
+
+    #[test]
+    fn fuzzy_apply_of_a_change_with_an_index_near_the_max_value_fails() {
+        let patch = Patch::from_changes(vec![change(usize::MAX - 1, 0, vec![1, 2], vec![3])]);
+
+        let applied = patch.fuzzy().apply(&[1, 2, 3]);
+
+        assert_that!(applied.outcomes()).is_equal_to(&[ChangeOutcome::Failed][..]);
+        assert_that!(applied.into_patched()).is_equal_to(vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn fuzzy_apply_to_the_original_left_sequence() {
+        let left = ['l', 'o', 'r', 'e', 'm'];
+        let right = ['L', 'o', 'r', 'e', 'm', '!'];
+        let patch = Patch::with_context(&left, &right, 2);
+
+        let applied = patch.fuzzy().apply(&left);
+
+        assert_that!(applied.is_complete()).is_true();
+        assert_that!(applied.outcomes()).is_equal_to(
+            &[
+                ChangeOutcome::Applied { fuzz: 0 },
+                ChangeOutcome::Applied { fuzz: 0 },
+            ][..],
+        );
+        assert_that!(applied.into_patched()).is_equal_to(right.to_vec());
+    }
+
+    #[test]
+    fn fuzzy_apply_to_a_sequence_with_elements_inserted_before_the_changes() {
+        let left = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let right = [1, 2, 0, 4, 5, 6, 7, 0, 9];
+        let patch = Patch::with_context(&left, &right, 1);
+
+        let applied = patch.fuzzy().apply(&[10, 11, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        assert_that!(applied.outcomes()).is_equal_to(
+            &[
+                ChangeOutcome::AppliedAtOffset { offset: 2, fuzz: 0 },
+                ChangeOutcome::AppliedAtOffset { offset: 2, fuzz: 0 },
+            ][..],
+        );
+        assert_that!(applied.patched()).is_equal_to(&[10, 11, 1, 2, 0, 4, 5, 6, 7, 0, 9][..]);
+    }
+
+    #[test]
+    fn fuzzy_apply_to_a_sequence_with_elements_removed_before_the_change() {
+        let left = [1, 2, 3, 4, 5, 6];
+        let right = [1, 2, 3, 4, 0, 6];
+        let patch = Patch::with_context(&left, &right, 1);
+
+        let applied = patch.fuzzy().apply(&[3, 4, 5, 6]);
+
+        assert_that!(applied.outcomes()).is_equal_to(
+            &[ChangeOutcome::AppliedAtOffset {
+                offset: -2,
+                fuzz: 0,
+            }][..],
+        );
+        assert_that!(applied.patched()).is_equal_to(&[3, 4, 0, 6][..]);
+    }
+
+    #[test]
+    fn change_beyond_the_max_offset_is_not_applied() {
+        let left = [1, 2, 3, 4];
+        let right = [1, 2, 0, 4];
+        let patch = Patch::with_context(&left, &right, 1);
+
+        let applied = patch.fuzzy().max_offset(2).apply(&[7, 7, 7, 1, 2, 3, 4]);
+
+        assert_that!(applied.is_complete()).is_false();
+        assert_that!(applied.outcomes()).is_equal_to(&[ChangeOutcome::Failed][..]);
+        assert_that!(applied.patched()).is_equal_to(&[7, 7, 7, 1, 2, 3, 4][..]);
+    }
+
+    #[test]
+    fn outer_context_elements_are_ignored_up_to_the_fuzz_factor() {
+        let left = [1, 2, 3, 4, 5, 6, 7];
+        let right = [1, 2, 3, 0, 5, 6, 7];
+        let patch = Patch::with_context(&left, &right, 3);
+
+        let applied = patch.fuzzy().fuzz(1).apply(&[9, 2, 3, 4, 5, 6, 9]);
+        let not_applied = patch.fuzzy().fuzz(0).apply(&[9, 2, 3, 4, 5, 6, 9]);
+
+        assert_that!(applied.outcomes()).is_equal_to(&[ChangeOutcome::Applied { fuzz: 1 }][..]);
+        assert_that!(applied.patched()).is_equal_to(&[9, 2, 3, 0, 5, 6, 9][..]);
+        assert_that!(not_applied.outcomes()).is_equal_to(&[ChangeOutcome::Failed][..]);
+    }
+
+    #[test]
+    fn at_least_one_context_element_is_kept_regardless_of_the_fuzz_factor() {
+        let left = [1, 2, 3, 4];
+        let right = [1, 2, 0, 3, 4];
+        let patch = Patch::with_context(&left, &right, 2);
+
+        let applied = patch.fuzzy().fuzz(2).apply(&[7, 8, 9, 10, 11, 12]);
+
+        assert_that!(applied.outcomes()).is_equal_to(&[ChangeOutcome::Failed][..]);
+        assert_that!(applied.is_complete()).is_false();
+        assert_that!(applied.patched()).is_equal_to(&[7, 8, 9, 10, 11, 12][..]);
+    }
+
+    #[test]
+    fn the_fuzz_factor_is_limited_to_all_but_one_context_element() {
+        let left = [1, 2, 3, 4, 5];
+        let right = [1, 2, 0, 4, 5];
+        let patch = Patch::with_context(&left, &right, 2);
+
+        let applied = patch.fuzzy().fuzz(5).apply(&[9, 2, 3, 4, 9]);
+
+        assert_that!(applied.outcomes()).is_equal_to(&[ChangeOutcome::Applied { fuzz: 1 }][..]);
+        assert_that!(applied.patched()).is_equal_to(&[9, 2, 0, 4, 9][..]);
+    }
+
+    #[test]
+    fn failed_changes_are_skipped_and_the_other_changes_are_applied() {
+        let left = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let right = [1, 0, 3, 4, 5, 6, 7, 0, 9];
+        let patch = Patch::with_context(&left, &right, 1);
+
+        let applied = patch.fuzzy().fuzz(0).apply(&[1, 5, 3, 4, 5, 6, 7, 8, 9]);
+
+        assert_that!(applied.outcomes())
+            .is_equal_to(&[ChangeOutcome::Failed, ChangeOutcome::Applied { fuzz: 0 }][..]);
+        assert_that!(applied.patched()).is_equal_to(&[1, 5, 3, 4, 5, 6, 7, 0, 9][..]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Patience diff algorithm.
+//!
+//! The patience diff anchors the diff on the elements that occur exactly once
+//! in each of the two sequences. Of these unique common elements it keeps the
+//! longest subsequence that is in the same order in both sequences, which is
+//! found by patience sorting. The gaps between two anchors are diffed
+//! recursively. A gap without unique common elements is diffed using the
+//! Myers algorithm.
+//!
+//! Like the Myers algorithm, the common prefix and the common suffix are
+//! stripped off before searching for unique elements.
+
+use crate::myers::{self, common_prefix_len, common_suffix_len, DiffList};
+use crate::std::{collections::BTreeMap, ops::Range, vec, vec::Vec};
+use crate::{Capture, Diff, DiffError, Limits};
+
+/// Find the common subsequences and differences between two slices using
+/// the patience diff algorithm.
+///
+/// The limits apply to the parts that are diffed using the Myers
+/// algorithm, see [`Limits`].
+///
+/// Returns an error if the memory needed for the search can not be
+/// allocated.
+pub fn diff<T>(left: &[T], right: &[T], limits: &mut Limits<'_>) -> Result<Vec<Diff>, DiffError>
+where
+    T: Ord,
+{
+    if left.len() + right.len() == 0 {
+        return Ok(vec![Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 0,
+        }]);
+    }
+
+    let mut patience = Patience {
+        left,
+        right,
+        limits,
+        diffs: DiffList::default(),
+    };
+    patience.diff_range(0..left.len(), 0..right.len())?;
+    Ok(patience.diffs.into_vec())
+}
+
+/// State of the recursive search.
+struct Patience<'a, 'l, 'd, T> {
+    left: &'a [T],
+    right: &'a [T],
+    limits: &'l mut Limits<'d>,
+    diffs: DiffList<Capture>,
+}
+
+impl<T> Patience<'_, '_, '_, T>
+where
+    T: Ord,
+{
+    /// Lists the diffs of the subsequences `left[left_range]` and
+    /// `right[right_range]`.
+    fn diff_range(
+        &mut self,
+        left_range: Range<usize>,
+        right_range: Range<usize>,
+    ) -> Result<(), DiffError> {
+        let left = &self.left[left_range.clone()];
+        let right = &self.right[right_range.clone()];
+        let prefix_len = common_prefix_len(left, right, &mut PartialEq::eq);
+        let suffix_len = common_suffix_len(
+            &left[prefix_len..],
+            &right[prefix_len..],
+            &mut PartialEq::eq,
+        );
+        let left_start = left_range.start + prefix_len;
+        let left_end = left_range.end - suffix_len;
+        let right_start = right_range.start + prefix_len;
+        let right_end = right_range.end - suffix_len;
+
+        self.diffs
+            .push_both(left_range.start, right_range.start, prefix_len);
+        if left_start == left_end {
+            self.diffs.push_right(right_start, right_end - right_start);
+        } else if right_start == right_end {
+            self.diffs.push_left(left_start, left_end - left_start);
+        } else {
+            let anchors = unique_anchors(
+                &self.left[left_start..left_end],
+                &self.right[right_start..right_end],
+            );
+            if anchors.is_empty() {
+                let diffs = myers::diff_within_limits(
+                    &self.left[left_start..left_end],
+                    &self.right[right_start..right_end],
+                    PartialEq::eq,
+                    self.limits,
+                )?;
+                self.diffs.extend(&diffs, left_start, right_start);
+            } else {
+                let mut left_pos = left_start;
+                let mut right_pos = right_start;
+                for (left_index, right_index) in anchors {
+                    let left_anchor = left_start + left_index;
+                    let right_anchor = right_start + right_index;
+                    self.diff_range(left_pos..left_anchor, right_pos..right_anchor)?;
+                    self.diffs.push_both(left_anchor, right_anchor, 1);
+                    left_pos = left_anchor + 1;
+                    right_pos = right_anchor + 1;
+                }
+                self.diff_range(left_pos..left_end, right_pos..right_end)?;
+            }
+        }
+        self.diffs.push_both(left_end, right_end, suffix_len);
+        Ok(())
+    }
+}
+
+/// Occurrences of an element in the two sequences.
+#[derive(Default)]
+struct Occurrences {
+    left_count: usize,
+    left_index: usize,
+    right_count: usize,
+    right_index: usize,
+}
+
+/// Returns the pairs of indices of the elements that occur exactly once in
+/// each of the two slices and that form the longest subsequence of such
+/// elements in the same order in both slices.
+fn unique_anchors<T>(left: &[T], right: &[T]) -> Vec<(usize, usize)>
+where
+    T: Ord,
+{
+    let mut occurrences: BTreeMap<&T, Occurrences> = BTreeMap::new();
+    for (index, element) in left.iter().enumerate() {
+        let occurrence = occurrences.entry(element).or_default();
+        occurrence.left_count += 1;
+        occurrence.left_index = index;
+    }
+    for (index, element) in right.iter().enumerate() {
+        if let Some(occurrence) = occurrences.get_mut(element) {
+            occurrence.right_count += 1;
+            occurrence.right_index = index;
+        }
+    }
+
+    let mut candidates = occurrences
+        .values()
+        .filter(|occurrence| occurrence.left_count == 1 && occurrence.right_count == 1)
+        .map(|occurrence| (occurrence.left_index, occurrence.right_index))
+        .collect::<Vec<_>>();
+    candidates.sort_unstable();
+    longest_increasing_subsequence(&candidates)
+}
+
+/// Returns the longest subsequence of the candidates, which are sorted by
+/// their left index, whose right indices are increasing.
+///
+/// The subsequence is found by patience sorting: each candidate is placed on
+/// the leftmost pile whose top has a greater right index, remembering the
+/// top of the pile to its left as predecessor.
+fn longest_increasing_subsequence(candidates: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut tops: Vec<usize> = Vec::new();
+    let mut predecessors: Vec<Option<usize>> = Vec::with_capacity(candidates.len());
+    for (index, &(_, right_index)) in candidates.iter().enumerate() {
+        let pile = tops.partition_point(|&top| candidates[top].1 < right_index);
+        predecessors.push(pile.checked_sub(1).map(|previous| tops[previous]));
+        if pile == tops.len() {
+            tops.push(index);
+        } else {
+            tops[pile] = index;
+        }
+    }
+
+    let mut subsequence = Vec::with_capacity(tops.len());
+    let mut next = tops.last().copied();
+    while let Some(index) = next {
+        subsequence.push(candidates[index]);
+        next = predecessors[index];
+    }
+    subsequence.reverse();
+    subsequence
+}
+
+#[cfg(test)]
+mod tests;
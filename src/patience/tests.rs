@@ -0,0 +1,122 @@
+use super::*;
+use crate::diff as diff_myers;
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use crate::assertions::assert_diffs_transform;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn patience_diffs_transform_left_into_right(
+            left in prop::collection::vec(0..12_u8, 0..=60),
+            right in prop::collection::vec(0..12_u8, 0..=60),
+        ) {
+            let diffs = diff(&left, &right, &mut Limits::new()).unwrap_or_else(|err| panic!("{err}"));
+
+            assert_diffs_transform(&left, &right, &diffs)?;
+        }
+    }
+}
+
+#[test]
+fn patience_diff_of_two_empty_sequences() {
+    let left: [u8; 0] = [];
+    let right: [u8; 0] = [];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 0,
+    }]);
+}
+
+#[test]
+fn patience_diff_anchors_on_unique_lines() {
+    let left = ["fn a() {", "    a", "}", "fn b() {", "    b", "}"];
+    let right = ["fn b() {", "    b", "}", "fn a() {", "    a", "}"];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Left {
+            index: 0,
+            length: 3,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Right {
+            index: 2,
+            length: 3,
+        },
+        Diff::Both {
+            left_index: 5,
+            right_index: 5,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn patience_diff_recurses_into_the_gaps_between_anchors() {
+    let left = ["a", "x", "b", "y", "y", "c"];
+    let right = ["a", "b", "y", "z", "c"];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(vec![
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 1,
+            length: 2,
+        },
+        Diff::Left {
+            index: 4,
+            length: 1,
+        },
+        Diff::Right {
+            index: 3,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 5,
+            right_index: 4,
+            length: 1,
+        },
+    ]);
+}
+
+#[test]
+fn patience_diff_without_unique_elements_is_the_same_as_the_myers_diff() {
+    let left = ['a', 'a', 'b', 'b', 'a'];
+    let right = ['b', 'b', 'a', 'a', 'b'];
+
+    let diffs = diff(&left, &right, &mut Limits::new());
+
+    assert_that!(diffs).has_value(diff_myers(&left, &right));
+}
+
+#[test]
+fn longest_increasing_subsequence_of_candidates() {
+    let candidates = [(0, 4), (1, 1), (2, 5), (3, 2), (4, 3), (5, 0)];
+
+    let subsequence = longest_increasing_subsequence(&candidates);
+
+    assert_that!(subsequence).contains_exactly([(1, 1), (3, 2), (4, 3)]);
+}
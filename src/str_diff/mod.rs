@@ -0,0 +1,148 @@
+//! Find the differences between two strings reported as byte ranges.
+
+use crate::std::{iter::Peekable, ops::Range, str::CharIndices, vec::Vec};
+use crate::{diff_str, try_diff_str, Diff, DiffError};
+
+/// A substring that is present in either of two strings or in both.
+///
+/// This is the same as [`Diff`] but with byte ranges into the original
+/// strings instead of `char` indices. The ranges can be used directly to
+/// slice the strings, like `&left[range]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StrDiff {
+    /// A substring that is only present in the left string.
+    ///
+    /// This is equivalent to a 'remove' in an edit script.
+    Left {
+        /// The byte range of the substring in the left string.
+        range: Range<usize>,
+    },
+
+    /// A common substring of both strings.
+    Both {
+        /// The byte range of the substring in the left string.
+        left_range: Range<usize>,
+        /// The byte range of the substring in the right string.
+        right_range: Range<usize>,
+    },
+
+    /// A substring that is only present in the right string.
+    ///
+    /// This is equivalent to an 'insert' in an edit script.
+    Right {
+        /// The byte range of the substring in the right string.
+        range: Range<usize>,
+    },
+}
+
+/// Find the common substrings and differences between two strings.
+///
+/// It finds the same differences as [`diff_str()`], but reports them as byte
+/// ranges into the two strings.
+///
+/// # Panics
+///
+/// Panics if one of the strings is longer than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated. See [`try_diff_str_ranges()`] for a variant that
+/// returns an error instead.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_str_ranges, StrDiff};
+///
+/// let left = "Grüße";
+/// let right = "Größe";
+///
+/// let diffs = diff_str_ranges(left, right);
+///
+/// let StrDiff::Left { range } = diffs[1].clone() else {
+///     panic!("expected a diff of type `StrDiff::Left`");
+/// };
+/// assert_eq!(&left[range], "ü");
+/// ```
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+#[must_use]
+pub fn diff_str_ranges(left: &str, right: &str) -> Vec<StrDiff> {
+    to_str_diffs(left, right, &diff_str(left, right))
+}
+
+/// Find the common substrings and differences between two strings.
+///
+/// This is the fallible variant of [`diff_str_ranges()`].
+pub fn try_diff_str_ranges(left: &str, right: &str) -> Result<Vec<StrDiff>, DiffError> {
+    try_diff_str(left, right).map(|diffs| to_str_diffs(left, right, &diffs))
+}
+
+/// Converts diffs with `char` indices into diffs with byte ranges.
+///
+/// The diffs must be in ascending order as returned by [`diff_str()`].
+fn to_str_diffs(left: &str, right: &str, diffs: &[Diff]) -> Vec<StrDiff> {
+    let mut left_offsets = ByteOffsets::new(left);
+    let mut right_offsets = ByteOffsets::new(right);
+    diffs
+        .iter()
+        .map(|diff| match *diff {
+            Diff::Left { index, length } => StrDiff::Left {
+                range: left_offsets.range(index, length),
+            },
+            Diff::Both {
+                left_index,
+                right_index,
+                length,
+            } => StrDiff::Both {
+                left_range: left_offsets.range(left_index, length),
+                right_range: right_offsets.range(right_index, length),
+            },
+            Diff::Right { index, length } => StrDiff::Right {
+                range: right_offsets.range(index, length),
+            },
+        })
+        .collect()
+}
+
+/// Maps `char` indices to byte offsets walking the string once from start to
+/// end.
+struct ByteOffsets<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    len: usize,
+    char_index: usize,
+}
+
+impl<'a> ByteOffsets<'a> {
+    fn new(string: &'a str) -> Self {
+        Self {
+            chars: string.char_indices().peekable(),
+            len: string.len(),
+            char_index: 0,
+        }
+    }
+
+    /// Returns the byte offset of the `char` at the given index.
+    ///
+    /// The index must not be less than the index of the previous call.
+    fn offset(&mut self, char_index: usize) -> usize {
+        debug_assert!(
+            char_index >= self.char_index,
+            "char indices must be in ascending order"
+        );
+        while self.char_index < char_index {
+            self.chars.next();
+            self.char_index += 1;
+        }
+        self.chars
+            .peek()
+            .map_or(self.len, |(byte_offset, _)| *byte_offset)
+    }
+
+    fn range(&mut self, char_index: usize, length: usize) -> Range<usize> {
+        let start = self.offset(char_index);
+        let end = self.offset(char_index + length);
+        start..end
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,91 @@
+use super::*;
+use asserting::prelude::*;
+
+#[test]
+fn diff_str_ranges_of_two_empty_strings() {
+    let diffs = diff_str_ranges("", "");
+
+    assert_that!(diffs).contains_exactly([StrDiff::Both {
+        left_range: 0..0,
+        right_range: 0..0,
+    }]);
+}
+
+#[test]
+fn diff_str_ranges_of_ascii_strings() {
+    let left = "ABCE";
+    let right = "ABDE";
+
+    let diffs = diff_str_ranges(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        StrDiff::Both {
+            left_range: 0..2,
+            right_range: 0..2,
+        },
+        StrDiff::Left { range: 2..3 },
+        StrDiff::Right { range: 2..3 },
+        StrDiff::Both {
+            left_range: 3..4,
+            right_range: 3..4,
+        },
+    ]);
+}
+
+#[test]
+fn diff_str_ranges_of_strings_with_multi_byte_chars() {
+    let left = "Grüße, Jürgen";
+    let right = "Größe, Jürg";
+
+    let diffs = diff_str_ranges(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        StrDiff::Both {
+            left_range: 0..2,
+            right_range: 0..2,
+        },
+        StrDiff::Left { range: 2..4 },
+        StrDiff::Right { range: 2..4 },
+        StrDiff::Both {
+            left_range: 4..14,
+            right_range: 4..14,
+        },
+        StrDiff::Left { range: 14..16 },
+    ]);
+}
+
+#[test]
+fn ranges_of_diff_str_ranges_slice_the_original_strings() {
+    let left = "☀ sunny and warm ☺";
+    let right = "☁ cloudy and warm ☺";
+
+    let diffs = diff_str_ranges(left, right);
+
+    let mut left_parts = Vec::new();
+    let mut right_parts = Vec::new();
+    for diff in diffs {
+        match diff {
+            StrDiff::Left { range } => left_parts.push(&left[range]),
+            StrDiff::Both {
+                left_range,
+                right_range,
+            } => {
+                left_parts.push(&left[left_range]);
+                right_parts.push(&right[right_range]);
+            },
+            StrDiff::Right { range } => right_parts.push(&right[range]),
+        }
+    }
+    assert_that!(left_parts.concat()).is_equal_to(left);
+    assert_that!(right_parts.concat()).is_equal_to(right);
+}
+
+#[test]
+fn try_diff_str_ranges_returns_the_same_diffs_as_diff_str_ranges() {
+    let left = "abcabba";
+    let right = "cbabac";
+
+    let diffs = try_diff_str_ranges(left, right);
+
+    assert_that!(diffs).has_value(diff_str_ranges(left, right));
+}
@@ -4,18 +4,21 @@ use asserting::prelude::*;
 #[cfg(feature = "std")]
 mod properties {
     use super::*;
+    use crate::myers::reference::find_shortest_trace;
     use crate::std::string::String;
     use proptest::prelude::*;
 
     #[test]
-    #[allow(
-        clippy::cast_precision_loss,
-        clippy::cast_possible_truncation,
-        clippy::cast_sign_loss
-    )]
+    #[allow(clippy::cast_sign_loss)]
     fn max_sequence_length_value() {
-        assert_that!(max_sequence_length())
-            .is_equal_to(2 * ((isize::MAX as f64).sqrt() as usize - 2));
+        assert_that!(max_sequence_length()).is_equal_to((isize::MAX as usize - 3) / 4);
+    }
+
+    #[test]
+    fn the_diagonals_of_sequences_of_max_sequence_length_can_be_indexed() {
+        let max_depth = 2 * max_sequence_length();
+
+        assert_that!(2 * max_depth + 3).is_at_most(isize::MAX as usize);
     }
 
     proptest! {
@@ -326,3 +329,443 @@ mod diff_strings {
         ]);
     }
 }
+
+mod try_diff_sequences {
+    use super::*;
+    use crate::std::{format, vec};
+
+    #[test]
+    fn try_diff_str_returns_the_same_diffs_as_diff_str() {
+        let left = "abcabba";
+        let right = "cbabac";
+
+        let diffs = try_diff_str(left, right);
+
+        assert_that!(diffs).has_value(diff_str(left, right));
+    }
+
+    #[test]
+    fn left_sequence_is_longer_than_max_sequence_length() {
+        let left = vec![(); max_sequence_length() + 1];
+        let right = vec![(); 3];
+
+        let diffs = try_diff(&left, &right);
+
+        assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+            side: Side::Left,
+            length: max_sequence_length() + 1,
+            max_length: max_sequence_length(),
+        });
+    }
+
+    #[test]
+    fn right_sequence_is_longer_than_max_sequence_length() {
+        let left = vec![(); 3];
+        let right = vec![(); max_sequence_length() + 5];
+
+        let diffs = try_diff(&left, &right);
+
+        assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+            side: Side::Right,
+            length: max_sequence_length() + 5,
+            max_length: max_sequence_length(),
+        });
+    }
+
+    #[test]
+    fn error_message_of_sequence_too_long() {
+        let left = vec![(); 2];
+        let right = vec![(); max_sequence_length() + 1];
+
+        let diffs = try_diff(&left, &right);
+
+        assert_that!(diffs).has_error_message(format!(
+            "the right sequence is longer than the max supported length of {}, its length is {}",
+            max_sequence_length(),
+            max_sequence_length() + 1
+        ));
+    }
+}
+
+mod diff_with_custom_equality {
+    use super::*;
+    use crate::std::{string::String, vec, vec::Vec};
+
+    /// A record that does not implement `PartialEq`.
+    #[derive(Debug)]
+    struct Record {
+        id: u32,
+        name: &'static str,
+    }
+
+    fn records(records: &[(u32, &'static str)]) -> Vec<Record> {
+        records
+            .iter()
+            .map(|&(id, name)| Record { id, name })
+            .collect()
+    }
+
+    #[test]
+    fn diff_by_compares_elements_using_the_given_function() {
+        let left = records(&[(1, "lorem"), (2, "ipsum"), (3, "dolor")]);
+        let right = records(&[(1, "Lorem"), (3, "Dolor"), (4, "Sit")]);
+
+        let diffs = diff_by(&left, &right, |a, b| a.id == b.id);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Left {
+                index: 1,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 2,
+                right_index: 1,
+                length: 1,
+            },
+            Diff::Right {
+                index: 2,
+                length: 1,
+            },
+        ]);
+    }
+
+    #[test]
+    fn diff_by_compares_floats_within_a_tolerance() {
+        let left = [0.1, 0.2, 0.3, 0.4];
+        let right = [0.100_01, 0.25, 0.299_99, 0.4];
+
+        let diffs = diff_by(&left, &right, |a: &f64, b: &f64| (a - b).abs() < 0.001);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Left {
+                index: 1,
+                length: 1,
+            },
+            Diff::Right {
+                index: 1,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 2,
+                right_index: 2,
+                length: 2,
+            },
+        ]);
+    }
+
+    #[test]
+    fn diff_by_key_compares_the_keys_of_elements() {
+        let left = records(&[(1, "lorem"), (2, "ipsum"), (3, "dolor")]);
+        let right = records(&[(7, "lorem"), (8, "dolor")]);
+
+        let diffs = diff_by_key(&left, &right, |record| record.name);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Left {
+                index: 1,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 2,
+                right_index: 1,
+                length: 1,
+            },
+        ]);
+    }
+
+    #[test]
+    fn diff_by_key_calls_the_key_function_once_per_element() {
+        let left = vec!["lorem", "ipsum", "dolor"];
+        let right = vec!["Lorem", "Ipsum"];
+        let mut calls = 0;
+
+        let diffs = diff_by_key(&left, &right, |word| {
+            calls += 1;
+            word.to_lowercase()
+        });
+
+        assert_that!(calls).is_equal_to(5);
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 2,
+            },
+            Diff::Left {
+                index: 2,
+                length: 1,
+            },
+        ]);
+    }
+
+    #[test]
+    fn try_diff_by_returns_an_error_for_a_sequence_longer_than_max_sequence_length() {
+        let left = vec![(); 1];
+        let right = vec![(); max_sequence_length() + 1];
+
+        let diffs = try_diff_by(&left, &right, |(), ()| true);
+
+        assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+            side: Side::Right,
+            length: max_sequence_length() + 1,
+            max_length: max_sequence_length(),
+        });
+    }
+
+    #[test]
+    fn try_diff_by_key_returns_the_same_diffs_as_diff_by_key() {
+        let left = [String::from("Lorem"), String::from("ipsum")];
+        let right = [
+            String::from("lorem"),
+            String::from("IPSUM"),
+            String::from("dolor"),
+        ];
+
+        let diffs = try_diff_by_key(&left, &right, |word| word.to_lowercase());
+
+        assert_that!(diffs).has_value(diff_by_key(&left, &right, |word| word.to_lowercase()));
+    }
+}
+
+mod diff_with_algorithm {
+    use super::*;
+    use crate::std::vec;
+
+    #[test]
+    fn myers_algorithm_returns_the_same_diffs_as_diff() {
+        let left = ['a', 'b', 'c', 'a', 'b', 'b', 'a'];
+        let right = ['c', 'b', 'a', 'b', 'a', 'c'];
+
+        let diffs = diff_with_algorithm(&left, &right, Algorithm::Myers);
+
+        assert_that!(diffs).is_equal_to(diff(&left, &right));
+    }
+
+    #[test]
+    fn default_algorithm_is_myers() {
+        assert_that!(Algorithm::default()).is_equal_to(Algorithm::Myers);
+    }
+
+    #[test]
+    fn patience_algorithm_matches_unique_elements() {
+        let left = ["}", "fn a() {", "}", "fn b() {"];
+        let right = ["fn b() {", "}", "fn a() {", "}"];
+
+        let diffs = diff_with_algorithm(&left, &right, Algorithm::Patience);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Left {
+                index: 0,
+                length: 3,
+            },
+            Diff::Both {
+                left_index: 3,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Right {
+                index: 1,
+                length: 3,
+            },
+        ]);
+    }
+
+    #[test]
+    fn histogram_algorithm_matches_the_least_frequent_elements() {
+        let left = ["a", "b", "a", "c", "a"];
+        let right = ["c", "a", "b"];
+
+        let diffs = diff_with_algorithm(&left, &right, Algorithm::Histogram);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Left {
+                index: 0,
+                length: 3,
+            },
+            Diff::Both {
+                left_index: 3,
+                right_index: 0,
+                length: 2,
+            },
+            Diff::Right {
+                index: 2,
+                length: 1,
+            },
+        ]);
+    }
+
+    #[test]
+    fn onp_algorithm_finds_a_minimal_diff() {
+        let left = ['b', 'd'];
+        let right = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h'];
+
+        let diffs = diff_with_algorithm(&left, &right, Algorithm::Onp);
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Right {
+                index: 0,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 0,
+                right_index: 1,
+                length: 1,
+            },
+            Diff::Right {
+                index: 2,
+                length: 1,
+            },
+            Diff::Both {
+                left_index: 1,
+                right_index: 3,
+                length: 1,
+            },
+            Diff::Right {
+                index: 4,
+                length: 4,
+            },
+        ]);
+    }
+
+    #[test]
+    fn auto_algorithm_chooses_onp_for_sequences_with_skewed_lengths() {
+        assert_that!(Algorithm::Auto.resolve(2, 8)).is_equal_to(Algorithm::Onp);
+        assert_that!(Algorithm::Auto.resolve(9, 2)).is_equal_to(Algorithm::Onp);
+        assert_that!(Algorithm::Auto.resolve(0, 0)).is_equal_to(Algorithm::Onp);
+    }
+
+    #[test]
+    fn auto_algorithm_chooses_myers_for_sequences_with_similar_lengths() {
+        assert_that!(Algorithm::Auto.resolve(2, 7)).is_equal_to(Algorithm::Myers);
+        assert_that!(Algorithm::Auto.resolve(5, 5)).is_equal_to(Algorithm::Myers);
+    }
+
+    #[test]
+    fn try_diff_with_algorithm_returns_an_error_for_a_sequence_longer_than_max_sequence_length() {
+        let left = vec![(); max_sequence_length() + 1];
+        let right = vec![(); 1];
+
+        let diffs = try_diff_with_algorithm(&left, &right, Algorithm::Patience);
+
+        assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+            side: Side::Left,
+            length: max_sequence_length() + 1,
+            max_length: max_sequence_length(),
+        });
+    }
+}
+
+mod diff_with_limits {
+    use super::*;
+
+    #[test]
+    fn diff_with_limits_that_are_not_exceeded_returns_the_same_diffs_as_diff() {
+        let left = ['a', 'b', 'c', 'a', 'b', 'b', 'a'];
+        let right = ['c', 'b', 'a', 'b', 'a', 'c'];
+
+        let diffs = diff_with_limits(&left, &right, Limits::new().max_cost(100));
+
+        assert_that!(diffs).is_equal_to(diff(&left, &right));
+    }
+
+    #[test]
+    fn diff_with_an_exceeded_deadline_lists_all_elements_between_prefix_and_suffix_as_replaced() {
+        let left = ['a', 'b', 'c', 'd', 'e'];
+        let right = ['a', 'c', 'b', 'd', 'e'];
+
+        let diffs = diff_with_limits(&left, &right, Limits::new().deadline(|| true));
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Both {
+                left_index: 0,
+                right_index: 0,
+                length: 1,
+            },
+            Diff::Left {
+                index: 1,
+                length: 2,
+            },
+            Diff::Right {
+                index: 1,
+                length: 2,
+            },
+            Diff::Both {
+                left_index: 3,
+                right_index: 3,
+                length: 2,
+            },
+        ]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn diff_with_an_instant_in_the_past_as_deadline() {
+        let left = ['a', 'b', 'c'];
+        let right = ['c', 'b', 'a'];
+
+        let diffs = diff_with_limits(
+            &left,
+            &right,
+            Limits::new().deadline(std::time::Instant::now()),
+        );
+
+        assert_that!(diffs).contains_exactly([
+            Diff::Left {
+                index: 0,
+                length: 3,
+            },
+            Diff::Right {
+                index: 0,
+                length: 3,
+            },
+        ]);
+    }
+
+    #[test]
+    fn onp_algorithm_exceeding_the_max_cost_falls_back_to_the_approximation_of_myers() {
+        let left = ['x', 'b', 'c', 'y'];
+        let right = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l'];
+        let mut limits = Limits::new().max_cost(1);
+
+        let diffs = diff_within_limits(&left, &right, Algorithm::Onp, &mut limits);
+
+        assert_that!(diffs).has_value(diff_with_limits(&left, &right, Limits::new().max_cost(1)));
+    }
+
+    #[test]
+    fn try_diff_with_limits_returns_an_error_for_a_sequence_longer_than_max_sequence_length() {
+        let left = crate::std::vec![(); 2];
+        let right = crate::std::vec![(); max_sequence_length() + 1];
+
+        let diffs = try_diff_with_limits(&left, &right, Limits::new());
+
+        assert_that!(diffs).has_error(DiffError::SequenceTooLong {
+            side: Side::Right,
+            length: max_sequence_length() + 1,
+            max_length: max_sequence_length(),
+        });
+    }
+
+    #[test]
+    fn debug_format_of_limits() {
+        let limits = Limits::new().max_cost(10).deadline(|| false);
+
+        assert_that!(crate::std::format!("{limits:?}"))
+            .is_equal_to("Limits { max_cost: Some(10), deadline: Some(\"...\") }");
+    }
+}
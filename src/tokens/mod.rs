@@ -0,0 +1,241 @@
+//! Find the differences between two texts token by token, e.g. word by word.
+
+use crate::std::{ops::Range, vec::Vec};
+use crate::{diff, try_diff, Diff, DiffError, DiffOptions, Granularity};
+
+/// Splits a text into tokens.
+///
+/// The tokens returned by a tokenizer must be consecutive slices of the text
+/// that together cover the whole text without gaps. This way the indices of
+/// the [`Diff`]s found by [`diff_tokens()`] can be mapped back to byte
+/// offsets in the original text, which is what [`token_range()`] does.
+pub trait Tokenizer {
+    /// Splits the given text into tokens.
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str>;
+}
+
+impl<Z> Tokenizer for &Z
+where
+    Z: Tokenizer + ?Sized,
+{
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        (**self).tokenize(text)
+    }
+}
+
+/// Splits a text at the boundaries between whitespace and non-whitespace.
+///
+/// Each token is either a run of whitespace or a run of non-whitespace
+/// characters. Punctuation sticks to the adjacent word, e.g. `"lorem, ipsum"`
+/// is split into `"lorem,"`, `" "` and `"ipsum"`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        split_into_runs(text, |chr| {
+            if chr.is_whitespace() {
+                CharClass::Whitespace
+            } else {
+                CharClass::Other
+            }
+        })
+    }
+}
+
+/// Splits a text at word boundaries.
+///
+/// Each token is either a word or a run of characters between two words. A
+/// word is a run of alphanumeric characters and underscores (`_`). For
+/// example, `"lorem, ipsum"` is split into `"lorem"`, `", "` and `"ipsum"`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WordBoundaryTokenizer;
+
+impl Tokenizer for WordBoundaryTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        split_into_runs(text, |chr| {
+            if is_word_char(chr) {
+                CharClass::Word
+            } else {
+                CharClass::Other
+            }
+        })
+    }
+}
+
+/// Splits a text into words, runs of whitespace and single punctuation
+/// characters.
+///
+/// A word is a run of alphanumeric characters and underscores (`_`). Any
+/// other character that is not whitespace is a token on its own. For
+/// example, `"lorem, ipsum!?"` is split into `"lorem"`, `","`, `" "`,
+/// `"ipsum"`, `"!"` and `"?"`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PunctuationTokenizer;
+
+impl Tokenizer for PunctuationTokenizer {
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        split_into_runs(text, |chr| {
+            if is_word_char(chr) {
+                CharClass::Word
+            } else if chr.is_whitespace() {
+                CharClass::Whitespace
+            } else {
+                CharClass::Single
+            }
+        })
+    }
+}
+
+/// Find the common words and differences between two texts.
+///
+/// The texts are split into tokens using the [`WordBoundaryTokenizer`], so
+/// each token is either a word or the characters between two words. Use
+/// [`diff_tokens()`] to split the texts with another [`Tokenizer`].
+///
+/// The indices and lengths of the returned [`Diff`]s count tokens. Use
+/// [`token_range()`] to get the byte range of the tokens in the original
+/// text.
+///
+/// # Panics
+///
+/// Panics if one of the texts has more tokens than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated. See [`try_diff_words()`] for a variant that returns
+/// an error instead.
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+#[must_use]
+pub fn diff_words(left: &str, right: &str) -> Vec<Diff> {
+    DiffOptions::new()
+        .granularity(Granularity::Words)
+        .diff_str(left, right)
+}
+
+/// Find the common words and differences between two texts.
+///
+/// This is the fallible variant of [`diff_words()`].
+pub fn try_diff_words(left: &str, right: &str) -> Result<Vec<Diff>, DiffError> {
+    DiffOptions::new()
+        .granularity(Granularity::Words)
+        .try_diff_str(left, right)
+}
+
+/// Find the common tokens and differences between two texts, which are split
+/// into tokens by the given [`Tokenizer`].
+///
+/// The indices and lengths of the returned [`Diff`]s count tokens. Use
+/// [`token_range()`] with the same tokenizer to get the byte range of the
+/// tokens in the original text.
+///
+/// # Panics
+///
+/// Panics if one of the texts has more tokens than the max supported length
+/// [`max_sequence_length()`] or if the memory needed to find the differences
+/// can not be allocated. See [`try_diff_tokens()`] for a variant that returns
+/// an error instead.
+///
+/// [`max_sequence_length()`]: crate::max_sequence_length
+#[must_use]
+pub fn diff_tokens<Z>(left: &str, right: &str, tokenizer: Z) -> Vec<Diff>
+where
+    Z: Tokenizer,
+{
+    diff(&tokenizer.tokenize(left), &tokenizer.tokenize(right))
+}
+
+/// Find the common tokens and differences between two texts, which are split
+/// into tokens by the given [`Tokenizer`].
+///
+/// This is the fallible variant of [`diff_tokens()`].
+pub fn try_diff_tokens<Z>(left: &str, right: &str, tokenizer: Z) -> Result<Vec<Diff>, DiffError>
+where
+    Z: Tokenizer,
+{
+    try_diff(&tokenizer.tokenize(left), &tokenizer.tokenize(right))
+}
+
+/// Returns the byte range of `length` tokens starting at the token `index`
+/// within the given `text`.
+///
+/// The text is split into tokens by the given [`Tokenizer`], which must be
+/// the same one that was used to find the [`Diff`]s. Returns `None` if the
+/// tokens are not within the text.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{diff_words, token_range, Diff, WordBoundaryTokenizer};
+///
+/// let left = "the quick brown fox";
+/// let right = "the slow brown fox";
+///
+/// let diffs = diff_words(left, right);
+///
+/// let Diff::Right { index, length } = diffs[2] else {
+///     panic!("expected a diff of type `Diff::Right`");
+/// };
+/// let range = token_range(right, WordBoundaryTokenizer, index, length);
+///
+/// assert_eq!(range, Some(4..8));
+/// assert_eq!(&right[4..8], "slow");
+/// ```
+#[must_use]
+pub fn token_range<Z>(text: &str, tokenizer: Z, index: usize, length: usize) -> Option<Range<usize>>
+where
+    Z: Tokenizer,
+{
+    let tokens = tokenizer.tokenize(text);
+    let end_index = index.checked_add(length)?;
+    if end_index > tokens.len() {
+        return None;
+    }
+    let start = tokens[..index].iter().map(|token| token.len()).sum();
+    let end = start
+        + tokens[index..end_index]
+            .iter()
+            .map(|token| token.len())
+            .sum::<usize>();
+    Some(start..end)
+}
+
+/// Classification of characters used to split a text into runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Word,
+    Whitespace,
+    Other,
+    /// Each character of this class is a token on its own.
+    Single,
+}
+
+fn is_word_char(chr: char) -> bool {
+    chr.is_alphanumeric() || chr == '_'
+}
+
+/// Splits the text into runs of characters of the same class.
+fn split_into_runs<F>(text: &str, classify: F) -> Vec<&str>
+where
+    F: Fn(char) -> CharClass,
+{
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut last_class = None;
+    for (index, chr) in text.char_indices() {
+        let class = classify(chr);
+        if let Some(last_class) = last_class {
+            if class != last_class || class == CharClass::Single {
+                tokens.push(&text[start..index]);
+                start = index;
+            }
+        }
+        last_class = Some(class);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,208 @@
+use super::*;
+use asserting::prelude::*;
+
+mod tokenizers {
+    use super::*;
+
+    #[test]
+    fn whitespace_tokenizer_splits_at_whitespace_boundaries() {
+        let tokens = WhitespaceTokenizer.tokenize("lorem, ipsum  dolor\tsit(amet)");
+
+        assert_that!(tokens).contains_exactly([
+            "lorem,",
+            " ",
+            "ipsum",
+            "  ",
+            "dolor",
+            "\t",
+            "sit(amet)",
+        ]);
+    }
+
+    #[test]
+    fn word_boundary_tokenizer_splits_at_word_boundaries() {
+        let tokens = WordBoundaryTokenizer.tokenize("lorem, ipsum  dolor_sit(amet)");
+
+        assert_that!(tokens).contains_exactly([
+            "lorem",
+            ", ",
+            "ipsum",
+            "  ",
+            "dolor_sit",
+            "(",
+            "amet",
+            ")",
+        ]);
+    }
+
+    #[test]
+    fn punctuation_tokenizer_splits_punctuation_into_single_tokens() {
+        let tokens = PunctuationTokenizer.tokenize("lorem, ipsum  dolor?!(amet)");
+
+        assert_that!(tokens).contains_exactly([
+            "lorem", ",", " ", "ipsum", "  ", "dolor", "?", "!", "(", "amet", ")",
+        ]);
+    }
+
+    #[test]
+    fn tokenizers_split_non_ascii_text() {
+        let tokens = PunctuationTokenizer.tokenize("Grüße, Ünïcödé…");
+
+        assert_that!(tokens).contains_exactly(["Grüße", ",", " ", "Ünïcödé", "…"]);
+    }
+
+    #[test]
+    fn tokenizers_return_no_tokens_for_an_empty_text() {
+        assert_that!(WhitespaceTokenizer.tokenize("")).is_empty();
+        assert_that!(WordBoundaryTokenizer.tokenize("")).is_empty();
+        assert_that!(PunctuationTokenizer.tokenize("")).is_empty();
+    }
+}
+
+#[test]
+fn diff_words_of_sentences_with_a_replaced_word() {
+    let left = "the quick brown fox jumps";
+    let right = "the slow brown fox jumps";
+
+    let diffs = diff_words(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Left {
+            index: 2,
+            length: 1,
+        },
+        Diff::Right {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 3,
+            length: 6,
+        },
+    ]);
+}
+
+#[test]
+fn diff_words_does_not_split_words() {
+    let left = "lorem ipsum dolor";
+    let right = "lorem ipsam dolor";
+
+    let diffs = diff_words(left, right);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 2,
+        },
+        Diff::Left {
+            index: 2,
+            length: 1,
+        },
+        Diff::Right {
+            index: 2,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 3,
+            right_index: 3,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn diff_tokens_with_whitespace_tokenizer() {
+    let left = "lorem, ipsum";
+    let right = "lorem ipsum";
+
+    let diffs = diff_tokens(left, right, WhitespaceTokenizer);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Left {
+            index: 0,
+            length: 1,
+        },
+        Diff::Right {
+            index: 0,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 1,
+            right_index: 1,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn diff_tokens_with_punctuation_tokenizer() {
+    let left = "lorem, ipsum";
+    let right = "lorem ipsum";
+
+    let diffs = diff_tokens(left, right, PunctuationTokenizer);
+
+    assert_that!(diffs).contains_exactly([
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 1,
+        },
+        Diff::Both {
+            left_index: 2,
+            right_index: 1,
+            length: 2,
+        },
+    ]);
+}
+
+#[test]
+fn try_diff_words_returns_the_same_diffs_as_diff_words() {
+    let left = "the quick brown fox";
+    let right = "a quick red fox";
+
+    let diffs = try_diff_words(left, right);
+
+    assert_that!(diffs).has_value(diff_words(left, right));
+}
+
+#[test]
+fn token_range_maps_diffs_to_byte_ranges() {
+    let left = "Grüße, schöne Welt";
+    let right = "Grüße, große Welt";
+
+    let diffs = diff_words(left, right);
+
+    let Diff::Left { index, length } = diffs[1] else {
+        panic!("expected a diff of type `Diff::Left`");
+    };
+    let left_range = token_range(left, WordBoundaryTokenizer, index, length)
+        .unwrap_or_else(|| panic!("invalid range"));
+    let Diff::Right { index, length } = diffs[2] else {
+        panic!("expected a diff of type `Diff::Right`");
+    };
+    let right_range = token_range(right, WordBoundaryTokenizer, index, length)
+        .unwrap_or_else(|| panic!("invalid range"));
+
+    assert_that!(&left[left_range]).is_equal_to("schöne");
+    assert_that!(&right[right_range]).is_equal_to("große");
+}
+
+#[test]
+fn token_range_of_tokens_not_within_the_text() {
+    let text = "lorem ipsum";
+
+    assert_that!(token_range(text, WordBoundaryTokenizer, 3, 1)).is_none();
+    assert_that!(token_range(text, WordBoundaryTokenizer, 3, 0)).has_value(11..11);
+    assert_that!(token_range(text, WordBoundaryTokenizer, 4, 0)).is_none();
+}
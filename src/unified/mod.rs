@@ -0,0 +1,201 @@
+//! Rendering and parsing of line diffs in the unified diff format.
+//!
+//! The unified diff format is the format produced by `diff -u` and `git diff`
+//! and understood by tools like `patch`.
+
+mod parse;
+
+pub use parse::{parse_unified, FilePatch};
+
+use crate::lines::lines;
+use crate::patch::validate_diffs;
+use crate::std::{fmt, ops::Range, vec::Vec};
+use crate::{diff_lines, group_hunks, Diff, PatchError};
+
+/// The number of context lines around changes used by default.
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Renders the differences between two texts in the unified diff format.
+///
+/// The texts are compared line by line as done by [`diff_lines()`]. The
+/// unified diff is rendered by the [`Display`](fmt::Display) implementation,
+/// so it can be written to any formatter or converted into a `String` by
+/// calling `to_string()`.
+///
+/// Changed lines are grouped into hunks by [`group_hunks()`]. Each hunk
+/// contains a configurable number of unchanged context lines before and after
+/// the changes, which is [`DEFAULT_CONTEXT_LINES`] by default. If the two
+/// texts are equal, nothing is rendered.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::UnifiedDiff;
+///
+/// let left = "lorem\nipsum\ndolor\n";
+/// let right = "lorem\nipsum\ndolores\n";
+///
+/// let unified = UnifiedDiff::new(left, right)
+///     .labels("a/lorem.txt", "b/lorem.txt")
+///     .context_lines(1)
+///     .to_string();
+///
+/// assert_eq!(
+///     unified,
+///     "--- a/lorem.txt\n\
+///      +++ b/lorem.txt\n\
+///      @@ -2,2 +2,2 @@\n \
+///      ipsum\n\
+///      -dolor\n\
+///      +dolores\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnifiedDiff<'a> {
+    left: &'a str,
+    right: &'a str,
+    diffs: Vec<Diff>,
+    left_label: &'a str,
+    right_label: &'a str,
+    context_lines: usize,
+}
+
+impl<'a> UnifiedDiff<'a> {
+    /// Finds the differences between the two texts line by line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the texts has more lines than the max supported
+    /// length [`max_sequence_length()`] or if the memory needed to find the
+    /// differences can not be allocated.
+    ///
+    /// [`max_sequence_length()`]: crate::max_sequence_length
+    #[must_use]
+    pub fn new(left: &'a str, right: &'a str) -> Self {
+        Self::with_valid_diffs(left, right, diff_lines(left, right))
+    }
+
+    /// Renders the given differences between the two texts.
+    ///
+    /// The diffs must be the result of [`diff_lines()`] for the two texts or
+    /// of any other function that returns line diffs in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the diffs are not consistent with the lines of the
+    /// two texts, i.e. if a diff refers to lines out of bounds, does not
+    /// start where the previous diff ended or the diffs do not cover the
+    /// texts completely.
+    pub fn from_diffs(left: &'a str, right: &'a str, diffs: Vec<Diff>) -> Result<Self, PatchError> {
+        validate_diffs(lines(left).len(), lines(right).len(), &diffs)?;
+        Ok(Self::with_valid_diffs(left, right, diffs))
+    }
+
+    const fn with_valid_diffs(left: &'a str, right: &'a str, diffs: Vec<Diff>) -> Self {
+        Self {
+            left,
+            right,
+            diffs,
+            left_label: "left",
+            right_label: "right",
+            context_lines: DEFAULT_CONTEXT_LINES,
+        }
+    }
+
+    /// Sets the labels rendered in the `---` and `+++` header lines.
+    ///
+    /// Usually the labels are the paths of the files that are compared. By
+    /// default, the labels are `left` and `right`.
+    #[must_use]
+    pub const fn labels(mut self, left_label: &'a str, right_label: &'a str) -> Self {
+        self.left_label = left_label;
+        self.right_label = right_label;
+        self
+    }
+
+    /// Sets the number of unchanged lines rendered before and after changes.
+    #[must_use]
+    pub const fn context_lines(mut self, context_lines: usize) -> Self {
+        self.context_lines = context_lines;
+        self
+    }
+
+    /// Returns the line diffs that are rendered.
+    #[must_use]
+    pub fn diffs(&self) -> &[Diff] {
+        &self.diffs
+    }
+}
+
+impl fmt::Display for UnifiedDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hunks = group_hunks(&self.diffs, self.context_lines);
+        if hunks.is_empty() {
+            return Ok(());
+        }
+
+        let left_lines = self.left.split_inclusive('\n').collect::<Vec<_>>();
+        let right_lines = self.right.split_inclusive('\n').collect::<Vec<_>>();
+
+        writeln!(f, "--- {}", self.left_label)?;
+        writeln!(f, "+++ {}", self.right_label)?;
+        for hunk in hunks {
+            writeln!(
+                f,
+                "@@ -{} +{} @@",
+                HunkRange(hunk.left_range()),
+                HunkRange(hunk.right_range())
+            )?;
+            for diff in hunk.diffs() {
+                match *diff {
+                    Diff::Left { index, length } => {
+                        write_lines(f, '-', &left_lines[index..index + length])?;
+                    },
+                    Diff::Both {
+                        left_index, length, ..
+                    } => {
+                        write_lines(f, ' ', &left_lines[left_index..left_index + length])?;
+                    },
+                    Diff::Right { index, length } => {
+                        write_lines(f, '+', &right_lines[index..index + length])?;
+                    },
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes each line with the given prefix.
+///
+/// A line that does not end with a line feed is followed by the marker
+/// `\ No newline at end of file`.
+fn write_lines(f: &mut fmt::Formatter<'_>, prefix: char, lines: &[&str]) -> fmt::Result {
+    for line in lines {
+        write!(f, "{prefix}{line}")?;
+        if !line.ends_with('\n') {
+            f.write_str("\n\\ No newline at end of file\n")?;
+        }
+    }
+    Ok(())
+}
+
+/// The range of a hunk in one of the texts as rendered in the hunk header.
+///
+/// The start line is 1-based. The length is omitted if it is 1. An empty
+/// range starts at the line before the hunk.
+struct HunkRange(Range<usize>);
+
+impl fmt::Display for HunkRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let start = self.0.start;
+        match self.0.len() {
+            0 => write!(f, "{start},0"),
+            1 => write!(f, "{}", start + 1),
+            len => write!(f, "{},{len}", start + 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
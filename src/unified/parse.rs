@@ -0,0 +1,440 @@
+//! Parsing of texts in the unified diff format.
+
+use super::{write_lines, HunkRange};
+use crate::std::{fmt, iter::Peekable, mem, string::String, vec::Vec};
+use crate::{Change, ParseError, Patch, PatchError};
+
+/// Parses a text in the unified diff format into one [`FilePatch`] per file.
+///
+/// The text may contain the differences of several files, each starting with
+/// a `---` and a `+++` header line, as produced by `diff -u`. Git patches are
+/// supported as well. Each `diff --git` line starts a new file, and the
+/// extended header lines following it, like `index` or `new file mode`, are
+/// kept as header lines of the file.
+///
+/// Any text before the first file, like the message of a patch created by
+/// `git format-patch`, and after the last hunk of a file is ignored.
+///
+/// # Errors
+///
+/// Returns an error if a hunk header is not valid, if a hunk contains a line
+/// that is not a context line, a removed line, an inserted line or a
+/// `\ No newline at end of file` marker, or if a hunk has less lines than
+/// announced by its header.
+///
+/// # Example
+///
+/// ```
+/// use sdiff::{parse_unified, UnifiedDiff};
+///
+/// let left = "lorem\nipsum\ndolor\n";
+/// let right = "lorem\nipsum\ndolores\n";
+/// let unified = UnifiedDiff::new(left, right)
+///     .labels("a/lorem.txt", "b/lorem.txt")
+///     .to_string();
+///
+/// let files = parse_unified(&unified).unwrap_or_else(|err| panic!("{err}"));
+///
+/// assert_eq!(files.len(), 1);
+/// assert_eq!(files[0].left_label(), Some("a/lorem.txt"));
+/// assert_eq!(files[0].apply(left), Ok(right.to_string()));
+/// assert_eq!(files[0].to_string(), unified);
+/// ```
+pub fn parse_unified(text: &str) -> Result<Vec<FilePatch<'_>>, ParseError> {
+    let mut files = Vec::new();
+    let mut current: Option<FileParser<'_>> = None;
+    let mut lines = text
+        .split_inclusive('\n')
+        .enumerate()
+        .map(|(index, line)| (index + 1, line))
+        .peekable();
+
+    while let Some((number, line)) = lines.next() {
+        if line.starts_with("diff --git ") {
+            files.extend(current.take().map(FileParser::finish));
+            current = Some(FileParser::new(Some(line)));
+        } else if line.starts_with("--- ")
+            && lines
+                .peek()
+                .is_some_and(|(_, next)| next.starts_with("+++ "))
+        {
+            let mut file = match current.take() {
+                Some(file) if file.labels.is_none() => file,
+                other => {
+                    files.extend(other.map(FileParser::finish));
+                    FileParser::new(None)
+                },
+            };
+            let right_label = lines.next().map_or("", |(_, next)| label(next, "+++ "));
+            file.labels = Some((label(line, "--- "), right_label));
+            current = Some(file);
+        } else if line.starts_with("@@") {
+            let Some(file) = current.as_mut().filter(|file| file.labels.is_some()) else {
+                return Err(ParseError::MissingFileHeader { line: number });
+            };
+            file.parse_hunk(number, line, &mut lines)?;
+        } else if let Some(file) = current.as_mut().filter(|file| file.labels.is_none()) {
+            file.header_lines.push(line);
+        }
+    }
+    files.extend(current.map(FileParser::finish));
+    Ok(files)
+}
+
+/// The differences of one file in the unified diff format.
+///
+/// A file patch is either parsed from a text by [`parse_unified()`] or
+/// created from a [`Patch`] of lines by [`FilePatch::new()`]. It is rendered
+/// in the unified diff format by its [`Display`](fmt::Display)
+/// implementation. A file patch parsed from the output of [`UnifiedDiff`] is
+/// rendered exactly as the original text.
+///
+/// The elements of the patch are lines including their line terminators, the
+/// same way as the lines compared by [`diff_lines()`].
+///
+/// [`UnifiedDiff`]: crate::UnifiedDiff
+/// [`diff_lines()`]: crate::diff_lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilePatch<'a> {
+    header_lines: Vec<&'a str>,
+    labels: Option<(&'a str, &'a str)>,
+    patch: Patch<&'a str>,
+}
+
+impl<'a> FilePatch<'a> {
+    /// Creates a file patch from the given patch of lines.
+    ///
+    /// The labels are rendered in the `---` and `+++` header lines. The
+    /// context of the changes is rendered as context lines, so the patch
+    /// should have been created with context, like by
+    /// [`Patch::with_context()`].
+    #[must_use]
+    pub const fn new(left_label: &'a str, right_label: &'a str, patch: Patch<&'a str>) -> Self {
+        Self {
+            header_lines: Vec::new(),
+            labels: Some((left_label, right_label)),
+            patch,
+        }
+    }
+
+    /// Returns the lines before the `---` and `+++` header lines, like the
+    /// `diff --git` line and the extended header lines of a git patch.
+    ///
+    /// Each line includes its line terminator.
+    #[must_use]
+    pub fn header_lines(&self) -> &[&'a str] {
+        &self.header_lines
+    }
+
+    /// Returns the label of the `---` header line, usually the path of the
+    /// left file.
+    ///
+    /// Git patches that only rename a file or change its mode have no `---`
+    /// header line.
+    #[must_use]
+    pub fn left_label(&self) -> Option<&'a str> {
+        self.labels.map(|(left_label, _)| left_label)
+    }
+
+    /// Returns the label of the `+++` header line, usually the path of the
+    /// right file.
+    #[must_use]
+    pub fn right_label(&self) -> Option<&'a str> {
+        self.labels.map(|(_, right_label)| right_label)
+    }
+
+    /// Returns the patch of the lines of the file.
+    #[must_use]
+    pub const fn patch(&self) -> &Patch<&'a str> {
+        &self.patch
+    }
+
+    /// Returns the patch of the lines of the file.
+    #[must_use]
+    pub fn into_patch(self) -> Patch<&'a str> {
+        self.patch
+    }
+
+    /// Applies the patch to the given text and returns the patched text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text does not contain the removed lines and
+    /// their context lines at the expected positions. See [`Patch::fuzzy()`]
+    /// for applying a patch to a text that differs from the original one.
+    pub fn apply(&self, text: &str) -> Result<String, PatchError> {
+        let lines = text.split_inclusive('\n').collect::<Vec<_>>();
+        self.patch.apply(&lines).map(|lines| lines.concat())
+    }
+
+    /// Reverts the patch on the given text and returns the original text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the text does not contain the inserted lines and
+    /// their context lines at the expected positions.
+    pub fn revert(&self, text: &str) -> Result<String, PatchError> {
+        let lines = text.split_inclusive('\n').collect::<Vec<_>>();
+        self.patch.revert(&lines).map(|lines| lines.concat())
+    }
+}
+
+impl fmt::Display for FilePatch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.header_lines {
+            f.write_str(line)?;
+            if !line.ends_with('\n') {
+                f.write_str("\n")?;
+            }
+        }
+        if let Some((left_label, right_label)) = self.labels {
+            writeln!(f, "--- {left_label}")?;
+            writeln!(f, "+++ {right_label}")?;
+        }
+
+        let mut remaining = self.patch.changes();
+        while !remaining.is_empty() {
+            // changes are rendered in the same hunk, if their contexts cover
+            // the unchanged lines between them
+            let hunk_len = 1 + remaining
+                .windows(2)
+                .take_while(|pair| {
+                    gap_between(&pair[0], &pair[1])
+                        <= pair[0].trailing_context().len() + pair[1].leading_context().len()
+                })
+                .count();
+            let (hunk, rest) = remaining.split_at(hunk_len);
+            write_hunk(f, hunk)?;
+            remaining = rest;
+        }
+        Ok(())
+    }
+}
+
+/// Writes a hunk containing the given changes.
+fn write_hunk(f: &mut fmt::Formatter<'_>, hunk: &[Change<&str>]) -> fmt::Result {
+    let (Some(first), Some(last)) = (hunk.first(), hunk.last()) else {
+        return Ok(());
+    };
+    let leading_len = first.leading_context().len();
+    let trailing_len = last.trailing_context().len();
+    writeln!(
+        f,
+        "@@ -{} +{} @@",
+        HunkRange(
+            first.left_index().saturating_sub(leading_len)..last.left_range().end + trailing_len
+        ),
+        HunkRange(
+            first.right_index().saturating_sub(leading_len)..last.right_range().end + trailing_len
+        ),
+    )?;
+
+    write_lines(f, ' ', first.leading_context())?;
+    let mut previous: Option<&Change<&str>> = None;
+    for change in hunk {
+        if let Some(previous) = previous {
+            let gap = gap_between(previous, change);
+            let trailing = previous.trailing_context();
+            let from_trailing = gap.min(trailing.len());
+            let leading = change.leading_context();
+            let from_leading = (gap - from_trailing).min(leading.len());
+            write_lines(f, ' ', &trailing[..from_trailing])?;
+            write_lines(f, ' ', &leading[leading.len() - from_leading..])?;
+        }
+        write_lines(f, '-', change.removed())?;
+        write_lines(f, '+', change.inserted())?;
+        previous = Some(change);
+    }
+    write_lines(f, ' ', last.trailing_context())
+}
+
+/// The number of unchanged lines between two changes.
+fn gap_between<T>(previous: &Change<T>, next: &Change<T>) -> usize {
+    next.left_index().saturating_sub(previous.left_range().end)
+}
+
+/// Strips the prefix and the line terminator from a `---` or `+++` header
+/// line.
+fn label<'a>(line: &'a str, prefix: &str) -> &'a str {
+    let label = line.strip_prefix(prefix).unwrap_or(line);
+    label.strip_suffix('\n').unwrap_or(label)
+}
+
+/// Collects the header lines and the changes of one file.
+struct FileParser<'a> {
+    header_lines: Vec<&'a str>,
+    labels: Option<(&'a str, &'a str)>,
+    changes: Vec<Change<&'a str>>,
+}
+
+impl<'a> FileParser<'a> {
+    fn new(diff_line: Option<&'a str>) -> Self {
+        Self {
+            header_lines: diff_line.into_iter().collect(),
+            labels: None,
+            changes: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> FilePatch<'a> {
+        FilePatch {
+            header_lines: self.header_lines,
+            labels: self.labels,
+            patch: Patch::from_changes(self.changes),
+        }
+    }
+
+    /// Parses the lines of a hunk and adds its changes to the changes of the
+    /// file.
+    fn parse_hunk<I>(
+        &mut self,
+        number: usize,
+        header: &str,
+        lines: &mut Peekable<I>,
+    ) -> Result<(), ParseError>
+    where
+        I: Iterator<Item = (usize, &'a str)>,
+    {
+        let invalid_header = ParseError::InvalidHunkHeader { line: number };
+        let (left_start, mut left_remaining, right_start, mut right_remaining) =
+            parse_hunk_header(header).ok_or(invalid_header)?;
+        let mut left_index = start_index(left_start, left_remaining).ok_or(invalid_header)?;
+        let mut right_index = start_index(right_start, right_remaining).ok_or(invalid_header)?;
+
+        let mut hunk_lines: Vec<(u8, &'a str)> = Vec::new();
+        while left_remaining > 0 || right_remaining > 0 {
+            let Some((line_number, line)) = lines.next() else {
+                return Err(ParseError::IncompleteHunk { line: number });
+            };
+            let unexpected = ParseError::UnexpectedLine { line: line_number };
+            let (kind, content) = match line.as_bytes().first() {
+                Some(b'\n') => (b' ', line),
+                Some(b'\\') => {
+                    strip_line_terminator(&mut hunk_lines).ok_or(unexpected)?;
+                    continue;
+                },
+                Some(&kind) => (kind, &line[1..]),
+                None => return Err(unexpected),
+            };
+            match kind {
+                b' ' if left_remaining > 0 && right_remaining > 0 => {
+                    left_remaining -= 1;
+                    right_remaining -= 1;
+                },
+                b'-' if left_remaining > 0 => left_remaining -= 1,
+                b'+' if right_remaining > 0 => right_remaining -= 1,
+                _ => return Err(unexpected),
+            }
+            hunk_lines.push((kind, content));
+        }
+        if lines.next_if(|(_, line)| line.starts_with('\\')).is_some() {
+            strip_line_terminator(&mut hunk_lines);
+        }
+
+        let mut context = Vec::new();
+        let mut pending: Option<PendingChange<'a>> = None;
+        let mut hunk_changes: Vec<PendingChange<'a>> = Vec::new();
+        for (kind, content) in hunk_lines {
+            if kind == b' ' {
+                hunk_changes.extend(pending.take());
+                context.push(content);
+                left_index += 1;
+                right_index += 1;
+                continue;
+            }
+            let change = pending.get_or_insert_with(|| PendingChange {
+                left_index,
+                right_index,
+                removed: Vec::new(),
+                inserted: Vec::new(),
+                leading_context: mem::take(&mut context),
+            });
+            if kind == b'-' {
+                change.removed.push(content);
+                left_index += 1;
+            } else {
+                change.inserted.push(content);
+                right_index += 1;
+            }
+        }
+        hunk_changes.extend(pending);
+
+        let mut trailing_contexts = hunk_changes
+            .iter()
+            .skip(1)
+            .map(|change| change.leading_context.clone())
+            .chain(Some(context))
+            .collect::<Vec<_>>()
+            .into_iter();
+        for change in hunk_changes {
+            let trailing_context = trailing_contexts.next().unwrap_or_default();
+            self.changes.push(Change::new(
+                change.left_index,
+                change.right_index,
+                change.removed,
+                change.inserted,
+                change.leading_context,
+                trailing_context,
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// A change of a hunk whose trailing context is not known yet.
+struct PendingChange<'a> {
+    left_index: usize,
+    right_index: usize,
+    removed: Vec<&'a str>,
+    inserted: Vec<&'a str>,
+    leading_context: Vec<&'a str>,
+}
+
+/// Parses a hunk header of the form `@@ -l,s +l,s @@` into the 1-based start
+/// lines and the lengths of both ranges.
+///
+/// The length of a range may be omitted if it is 1. Any text after the
+/// closing `@@`, like the name of a function, is ignored.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, _) = rest.split_once(" @@")?;
+    let (left, right) = ranges.split_once(" +")?;
+    let (left_start, left_len) = parse_range(left)?;
+    let (right_start, right_len) = parse_range(right)?;
+    Some((left_start, left_len, right_start, right_len))
+}
+
+/// Parses the range `l,s` or `l` of a hunk header.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Converts the 1-based start line of a hunk range to a 0-based index.
+///
+/// An empty range starts at the line before the hunk. Returns `None` if the
+/// range does not start at a line or if its end is not representable.
+const fn start_index(start: usize, len: usize) -> Option<usize> {
+    let index = if len == 0 {
+        start
+    } else {
+        match start.checked_sub(1) {
+            Some(index) => index,
+            None => return None,
+        }
+    };
+    match index.checked_add(len) {
+        Some(_) => Some(index),
+        None => None,
+    }
+}
+
+/// Removes the line terminator of the last line for a
+/// `\ No newline at end of file` marker.
+fn strip_line_terminator(hunk_lines: &mut [(u8, &str)]) -> Option<()> {
+    let (_, content) = hunk_lines.last_mut()?;
+    *content = content.strip_suffix('\n').unwrap_or(content);
+    Some(())
+}
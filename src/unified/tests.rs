@@ -0,0 +1,427 @@
+use super::*;
+use crate::std::{string::ToString, vec};
+use crate::{ParseError, Patch, PatchError, Side};
+use asserting::prelude::*;
+
+#[cfg(feature = "std")]
+mod properties {
+    use super::*;
+    use crate::std::string::String;
+    use proptest::prelude::*;
+
+    fn text() -> impl Strategy<Value = String> {
+        prop::collection::vec(
+            prop::sample::select(&["a\n", "b\n", "c\n", "d\n", "e"][..]),
+            0..30,
+        )
+        .prop_map(|lines| lines.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn a_parsed_unified_diff_is_rendered_as_the_original_text(
+            left in text(),
+            right in text(),
+            context_lines in 0..5_usize,
+        ) {
+            let unified = UnifiedDiff::new(&left, &right)
+                .context_lines(context_lines)
+                .to_string();
+
+            let files = parse_unified(&unified).unwrap_or_else(|err| panic!("{err}"));
+
+            let rendered = files.iter().map(ToString::to_string).collect::<String>();
+            prop_assert_eq!(rendered, unified);
+        }
+
+        #[test]
+        fn a_parsed_unified_diff_transforms_left_into_right_and_back(
+            left in text(),
+            right in text(),
+            context_lines in 0..5_usize,
+        ) {
+            let unified = UnifiedDiff::new(&left, &right)
+                .context_lines(context_lines)
+                .to_string();
+
+            let files = parse_unified(&unified).unwrap_or_else(|err| panic!("{err}"));
+
+            prop_assume!(left != right);
+            prop_assert_eq!(files.len(), 1);
+            prop_assert_eq!(files[0].apply(&left), Ok(right.clone()));
+            prop_assert_eq!(files[0].revert(&right), Ok(left));
+        }
+    }
+}
+
+#[test]
+fn unified_diff_of_equal_texts_is_empty() {
+    let text = "lorem\nipsum\n";
+
+    let unified = UnifiedDiff::new(text, text).to_string();
+
+    assert_that!(unified).is_empty();
+}
+
+#[test]
+fn unified_diff_with_one_changed_line() {
+    let left = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\n";
+    let right = "one\ntwo\nthree\nfour\nFIVE\nsix\nseven\neight\nnine\n";
+
+    let unified = UnifiedDiff::new(left, right).to_string();
+
+    assert_that!(unified).is_equal_to(
+        "--- left\n\
+         +++ right\n\
+         @@ -2,7 +2,7 @@\n \
+         two\n \
+         three\n \
+         four\n\
+         -five\n\
+         +FIVE\n \
+         six\n \
+         seven\n \
+         eight\n",
+    );
+}
+
+#[test]
+fn unified_diff_with_labels_and_one_context_line() {
+    let left = "one\ntwo\nthree\nfour\nfive\n";
+    let right = "one\nthree\nfour\nfive\nsix\n";
+
+    let unified = UnifiedDiff::new(left, right)
+        .labels("a/numbers.txt", "b/numbers.txt")
+        .context_lines(1)
+        .to_string();
+
+    assert_that!(unified).is_equal_to(
+        "--- a/numbers.txt\n\
+         +++ b/numbers.txt\n\
+         @@ -1,3 +1,2 @@\n \
+         one\n\
+         -two\n \
+         three\n\
+         @@ -5 +4,2 @@\n \
+         five\n\
+         +six\n",
+    );
+}
+
+#[test]
+fn hunks_with_adjacent_context_are_merged() {
+    let left = "one\ntwo\nthree\nfour\nfive\n";
+    let right = "one\nthree\nfour\n4.5\nfive\n";
+
+    let unified = UnifiedDiff::new(left, right).context_lines(1).to_string();
+
+    assert_that!(unified).is_equal_to(
+        "--- left\n\
+         +++ right\n\
+         @@ -1,5 +1,5 @@\n \
+         one\n\
+         -two\n \
+         three\n \
+         four\n\
+         +4.5\n \
+         five\n",
+    );
+}
+
+#[test]
+fn unified_diff_without_context_lines() {
+    let left = "one\ntwo\nthree\n";
+    let right = "one\nthree\nfour\n";
+
+    let unified = UnifiedDiff::new(left, right).context_lines(0).to_string();
+
+    assert_that!(unified).is_equal_to(
+        "--- left\n\
+         +++ right\n\
+         @@ -2 +1,0 @@\n\
+         -two\n\
+         @@ -3,0 +3 @@\n\
+         +four\n",
+    );
+}
+
+#[test]
+fn unified_diff_of_an_empty_and_a_non_empty_text() {
+    let unified = UnifiedDiff::new("", "one\ntwo\n").to_string();
+
+    assert_that!(unified).is_equal_to(
+        "--- left\n\
+         +++ right\n\
+         @@ -0,0 +1,2 @@\n\
+         +one\n\
+         +two\n",
+    );
+}
+
+#[test]
+fn unified_diff_marks_a_missing_newline_at_the_end_of_file() {
+    let left = "one\ntwo\n";
+    let right = "one\ntwo";
+
+    let unified = UnifiedDiff::new(left, right).to_string();
+
+    assert_that!(unified).is_equal_to(
+        "--- left\n\
+         +++ right\n\
+         @@ -1,2 +1,2 @@\n \
+         one\n\
+         -two\n\
+         +two\n\
+         \\ No newline at end of file\n",
+    );
+}
+
+#[test]
+fn unified_diff_from_given_diffs() {
+    let left = "one\ntwo\n";
+    let right = "one\n2\n";
+    let diffs = diff_lines(left, right);
+
+    let unified =
+        UnifiedDiff::from_diffs(left, right, diffs.clone()).unwrap_or_else(|err| panic!("{err}"));
+
+    assert_that!(unified.diffs()).is_equal_to(&diffs[..]);
+    assert_that!(unified.to_string()).is_equal_to(UnifiedDiff::new(left, right).to_string());
+}
+
+#[test]
+fn unified_diff_from_diffs_out_of_bounds_of_the_texts_returns_an_error() {
+    let left = "one
+two
+";
+    let right = "one
+2
+";
+    let diffs = vec![
+        Diff::Both {
+            left_index: 0,
+            right_index: 0,
+            length: 1,
+        },
+        Diff::Left {
+            index: 1,
+            length: 5,
+        },
+        Diff::Right {
+            index: 1,
+            length: 1,
+        },
+    ];
+
+    let unified = UnifiedDiff::from_diffs(left, right, diffs);
+
+    assert_that!(unified).has_error(PatchError::OutOfBounds {
+        side: Side::Left,
+        index: 1,
+        length: 5,
+        sequence_length: 2,
+    });
+}
+
+#[test]
+fn unified_diff_from_diffs_not_covering_the_texts_returns_an_error() {
+    let left = "one
+two
+";
+    let right = "one
+2
+";
+    let diffs = vec![Diff::Both {
+        left_index: 0,
+        right_index: 0,
+        length: 1,
+    }];
+
+    let unified = UnifiedDiff::from_diffs(left, right, diffs);
+
+    assert_that!(unified).has_error(PatchError::Incomplete {
+        side: Side::Left,
+        covered_length: 1,
+        sequence_length: 2,
+    });
+}
+
+mod parse {
+    use super::*;
+    use crate::std::{vec, vec::Vec};
+
+    #[test]
+    fn file_patch_of_a_patch_with_context_is_rendered_like_unified_diff() {
+        let left = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\nten\n";
+        let right = "one\n2\nthree\nfour\nfive\nsix\nseven\neight\nnine\nten\neleven\n";
+        let left_lines = left.split_inclusive('\n').collect::<Vec<_>>();
+        let right_lines = right.split_inclusive('\n').collect::<Vec<_>>();
+        let patch = Patch::with_context(&left_lines, &right_lines, 2);
+
+        let file_patch = FilePatch::new("a/numbers.txt", "b/numbers.txt", patch);
+
+        assert_that!(file_patch.to_string()).is_equal_to(
+            UnifiedDiff::new(left, right)
+                .labels("a/numbers.txt", "b/numbers.txt")
+                .context_lines(2)
+                .to_string(),
+        );
+    }
+
+    #[test]
+    fn parse_empty_text() {
+        let files = parse_unified("");
+
+        assert_that!(files).has_value(vec![]);
+    }
+
+    #[test]
+    fn parse_multi_file_git_patch() {
+        let text = "From 1234 Mon Sep 17 00:00:00 2001\n\
+                    Subject: [PATCH] Update files\n\
+                    \n\
+                    diff --git a/lorem.txt b/lorem.txt\n\
+                    index 3be9c81..86e041d 100644\n\
+                    --- a/lorem.txt\n\
+                    +++ b/lorem.txt\n\
+                    @@ -1,3 +1,3 @@ section\n \
+                    lorem\n\
+                    -ipsum\n\
+                    +Ipsum\n \
+                    dolor\n\
+                    diff --git a/new.txt b/new.txt\n\
+                    new file mode 100644\n\
+                    index 0000000..3be9c81\n\
+                    --- /dev/null\n\
+                    +++ b/new.txt\n\
+                    @@ -0,0 +1 @@\n\
+                    +sit\n\
+                    diff --git a/old.txt b/renamed.txt\n\
+                    similarity index 100%\n\
+                    rename from old.txt\n\
+                    rename to renamed.txt\n\
+                    -- \n\
+                    2.43.0\n";
+
+        let files = parse_unified(text).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(files.len()).is_equal_to(3);
+        assert_that!(files[0].header_lines()).is_equal_to(
+            &[
+                "diff --git a/lorem.txt b/lorem.txt\n",
+                "index 3be9c81..86e041d 100644\n",
+            ][..],
+        );
+        assert_that!(files[0].left_label()).has_value("a/lorem.txt");
+        assert_that!(files[0].right_label()).has_value("b/lorem.txt");
+        assert_that!(files[0].apply("lorem\nipsum\ndolor\n"))
+            .has_value("lorem\nIpsum\ndolor\n".to_string());
+        assert_that!(files[1].left_label()).has_value("/dev/null");
+        assert_that!(files[1].apply("")).has_value("sit\n".to_string());
+        assert_that!(files[2].left_label()).is_none();
+        assert_that!(files[2].header_lines().len()).is_equal_to(6);
+        assert_that!(files[2].patch().is_empty()).is_true();
+    }
+
+    #[test]
+    fn parse_plain_multi_file_unified_diff() {
+        let text = "--- one.txt\t2024-01-01 10:00:00\n\
+                    +++ one.txt\t2024-01-02 10:00:00\n\
+                    @@ -1 +1 @@\n\
+                    -one\n\
+                    +1\n\
+                    --- two.txt\n\
+                    +++ two.txt\n\
+                    @@ -1,2 +1 @@\n\
+                    -two\n \
+                    2\n";
+
+        let files = parse_unified(text).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(files.len()).is_equal_to(2);
+        assert_that!(files[0].left_label()).has_value("one.txt\t2024-01-01 10:00:00");
+        assert_that!(files[0].apply("one\n")).has_value("1\n".to_string());
+        assert_that!(files[1].apply("two\n2\n")).has_value("2\n".to_string());
+        assert_that!(files[1].revert("2\n")).has_value("two\n2\n".to_string());
+    }
+
+    #[test]
+    fn parse_hunk_with_missing_newline_at_end_of_file() {
+        let text = "--- left\n\
+                    +++ right\n\
+                    @@ -1,2 +1,2 @@\n \
+                    one\n\
+                    -two\n\
+                    \\ No newline at end of file\n\
+                    +two\n";
+
+        let files = parse_unified(text).unwrap_or_else(|err| panic!("{err}"));
+
+        assert_that!(files[0].apply("one\ntwo")).has_value("one\ntwo\n".to_string());
+        assert_that!(files[0].to_string()).is_equal_to(text);
+    }
+
+    #[test]
+    fn apply_parsed_patch_to_a_text_not_matching_the_context() {
+        let text = "--- left\n\
+                    +++ right\n\
+                    @@ -1,2 +1,2 @@\n \
+                    one\n\
+                    -two\n\
+                    +2\n";
+        let files = parse_unified(text).unwrap_or_else(|err| panic!("{err}"));
+
+        let patched = files[0].apply("1\ntwo\n");
+
+        assert_that!(patched).has_error(PatchError::Mismatch {
+            side: Side::Left,
+            index: 1,
+        });
+    }
+
+    #[test]
+    fn parse_invalid_hunk_header() {
+        let text = "--- left\n+++ right\n@@ -1,x +1 @@\n-one\n";
+
+        let files = parse_unified(text);
+
+        assert_that!(files).has_error(ParseError::InvalidHunkHeader { line: 3 });
+    }
+
+    #[test]
+    fn parse_hunk_header_with_a_range_ending_beyond_the_max_index() {
+        let text = "--- left\n+++ right\n@@ -18446744073709551615,2 +1,2 @@\n one\n two\n";
+
+        let files = parse_unified(text);
+
+        assert_that!(files).has_error(ParseError::InvalidHunkHeader { line: 3 });
+    }
+
+    #[test]
+    fn parse_unexpected_line_within_hunk() {
+        let text = "--- left\n+++ right\n@@ -1,2 +1,2 @@\n one\n*two\n";
+
+        let files = parse_unified(text);
+
+        assert_that!(files).has_error(ParseError::UnexpectedLine { line: 5 });
+    }
+
+    #[test]
+    fn parse_incomplete_hunk() {
+        let text = "--- left\n+++ right\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n";
+
+        let files = parse_unified(text);
+
+        assert_that!(files).has_error(ParseError::IncompleteHunk { line: 3 });
+    }
+
+    #[test]
+    fn parse_hunk_without_file_header() {
+        let text = "@@ -1 +1 @@\n-one\n+1\n";
+
+        let files = parse_unified(text);
+
+        assert_that!(files)
+            .has_error_message("the hunk starting in line 1 is not preceded by a file header");
+    }
+}